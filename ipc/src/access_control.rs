@@ -0,0 +1,392 @@
+//! [AccessControl] impl.
+
+use ::core::fmt::Debug;
+use ::std::{
+    io,
+    path::{Path as StdPath, PathBuf},
+    sync::OnceLock,
+};
+
+use ::iceoryx2::{
+    node::NodeCreationFailure, port::port_identifiers::UniquePublisherId, prelude::*,
+    service::dynamic_config::publish_subscribe::DynamicConfig,
+};
+
+/// Hook deciding if messages from a sender should be received.
+pub type AuthorizeHook = Box<dyn Send + FnMut(&Sender) -> bool>;
+
+/// Details of the process a message was sent from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sender {
+    /// Process id of sender.
+    pub pid: i32,
+    /// User id of sender, if it could be determined.
+    pub uid: Option<u32>,
+}
+
+impl Sender {
+    /// Find the sender of a message using the publishers registered for a service.
+    ///
+    /// The user id is taken from the owner of the directory the sender node stores its
+    /// details in, which is unique to the node such that reused process ids are never
+    /// mistaken for the sender.
+    fn of(
+        dynamic_config: &DynamicConfig,
+        origin: UniquePublisherId,
+        node_dir: &StdPath,
+    ) -> Option<Self> {
+        let mut node_id = None;
+        dynamic_config.list_publishers(|details| {
+            if details.publisher_id == origin {
+                node_id = Some(details.node_id);
+                CallbackProgression::Stop
+            } else {
+                CallbackProgression::Continue
+            }
+        });
+
+        let node_id = node_id?;
+        Some(Self {
+            pid: node_id.pid().value(),
+            uid: owner_uid(&node_dir.join(node_id.value().to_string())),
+        })
+    }
+
+    /// Check if the sender is run by the same user as the current process.
+    ///
+    /// If either user id cannot be determined false is returned.
+    pub fn is_current_user(&self) -> bool {
+        matches!((self.uid, current_uid()), (Some(uid), Some(current)) if uid == current)
+    }
+}
+
+/// Restrict which processes messages may be received from.
+///
+/// Restricted access control is only supported on unix, on other platforms
+/// creating the ipc node fails.
+#[derive(Default)]
+pub enum AccessControl {
+    /// Use the shared service namespace and receive messages from any local process.
+    #[default]
+    Any,
+    /// Keep services in a directory only accessible by the current user and only
+    /// receive messages sent by processes of the current user.
+    ///
+    /// Shared memory segments are created by iceoryx2 such that only their owner may
+    /// access them, as long as its `dev_permissions` feature is not enabled.
+    ///
+    /// Processes need to use the same kind of access control to find each other.
+    SameUser,
+    /// Same as [AccessControl::SameUser], with messages additionally
+    /// only being received if the hook returns true for the sender.
+    Authorized(AuthorizeHook),
+}
+
+impl Debug for AccessControl {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Any => write!(f, "Any"),
+            Self::SameUser => write!(f, "SameUser"),
+            Self::Authorized(..) => f.debug_tuple("Authorized").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl AccessControl {
+    /// Create access control using a custom authorization hook.
+    pub fn authorized<F>(hook: F) -> Self
+    where
+        F: 'static + Send + FnMut(&Sender) -> bool,
+    {
+        Self::Authorized(Box::new(hook))
+    }
+
+    /// Get node config to use, if none the global config should be used.
+    ///
+    /// Restricted access control uses a root directory only accessible by the current
+    /// user, and a file prefix including the user id such that shared memory of
+    /// different users never collide.
+    ///
+    /// # Errors
+    /// If access control is restricted and the user directory could not be set up, in
+    /// which case the shared namespace is never used as a fallback.
+    pub(crate) fn config(&self) -> Result<Option<Config>, NodeCreationFailure> {
+        self.config_from(Config::global_config())
+    }
+
+    /// Get node config to use based on `base`, if none the global config should be used.
+    ///
+    /// # Errors
+    /// If access control is restricted and the user directory could not be set up.
+    fn config_from(&self, base: &Config) -> Result<Option<Config>, NodeCreationFailure> {
+        if matches!(self, Self::Any) {
+            return Ok(None);
+        }
+
+        let Some(uid) = current_uid() else {
+            ::log::error!("could not get user id, refusing to use shared ipc namespace");
+            return Err(NodeCreationFailure::InsufficientPermissions);
+        };
+
+        let mut config = base.clone();
+        let root = PathBuf::from(config.global.root_path().to_string()).join(format!("u{uid}"));
+        if let Err(err) = create_user_dir(&root, uid) {
+            ::log::error!(
+                "could not create user ipc directory {}, {err}",
+                root.display()
+            );
+            return Err(NodeCreationFailure::InsufficientPermissions);
+        }
+
+        let root_path = match root_path(&root) {
+            Ok(root_path) => root_path,
+            Err(err) => {
+                ::log::error!("could not use user ipc directory {}, {err}", root.display());
+                return Err(NodeCreationFailure::InternalError);
+            }
+        };
+        config.global.set_root_path(&root_path);
+
+        if let Err(err) = config
+            .global
+            .prefix
+            .push_bytes(format!("u{uid}_").as_bytes())
+        {
+            ::log::error!("could not create user ipc prefix, {err}");
+            return Err(NodeCreationFailure::InternalError);
+        }
+
+        Ok(Some(config))
+    }
+
+    /// Check if a message from given origin should be received, `node_dir` should be
+    /// the directory node details are stored in.
+    pub(crate) fn authorize(
+        &mut self,
+        dynamic_config: &DynamicConfig,
+        origin: UniquePublisherId,
+        node_dir: &StdPath,
+    ) -> bool {
+        if matches!(self, Self::Any) {
+            return true;
+        }
+
+        let Some(sender) = Sender::of(dynamic_config, origin, node_dir) else {
+            ::log::warn!("could not determine sender of ipc message");
+            return false;
+        };
+
+        if !sender.is_current_user() {
+            ::log::warn!("ipc message sent by process {} of another user", sender.pid);
+            return false;
+        }
+
+        match self {
+            Self::Any | Self::SameUser => true,
+            Self::Authorized(hook) => hook(&sender),
+        }
+    }
+}
+
+/// Get directory node details are stored in for a node config.
+pub(crate) fn node_dir(config: &Config) -> PathBuf {
+    PathBuf::from(config.global.node_dir().to_string())
+}
+
+/// Get user id of current process.
+fn current_uid() -> Option<u32> {
+    static CURRENT_UID: OnceLock<Option<u32>> = OnceLock::new();
+    *CURRENT_UID.get_or_init(effective_uid)
+}
+
+/// Get effective user id of current process.
+#[cfg(unix)]
+fn effective_uid() -> Option<u32> {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    Some(unsafe { ::libc::geteuid() })
+}
+
+/// Get effective user id of current process.
+#[cfg(not(unix))]
+const fn effective_uid() -> Option<u32> {
+    None
+}
+
+/// Get user id owning a file.
+#[cfg(unix)]
+fn owner_uid(path: &StdPath) -> Option<u32> {
+    use ::std::os::unix::fs::MetadataExt;
+    ::std::fs::symlink_metadata(path)
+        .ok()
+        .map(|metadata| metadata.uid())
+}
+
+/// Get user id owning a file.
+#[cfg(not(unix))]
+fn owner_uid(_path: &StdPath) -> Option<u32> {
+    None
+}
+
+/// Convert a path to an iceoryx2 path.
+#[cfg(unix)]
+fn root_path(path: &StdPath) -> Result<Path, SemanticStringError> {
+    use ::std::os::unix::ffi::OsStrExt;
+    Path::new(path.as_os_str().as_bytes())
+}
+
+/// Convert a path to an iceoryx2 path.
+#[cfg(not(unix))]
+fn root_path(path: &StdPath) -> Result<Path, SemanticStringError> {
+    Path::new(
+        path.to_str()
+            .ok_or(SemanticStringError::InvalidContent)?
+            .as_bytes(),
+    )
+}
+
+/// Create a directory only accessible by the given user, or verify an existing one is.
+///
+/// # Errors
+/// If the directory could not be created, or exists and is not a directory
+/// owned by and only accessible to the user.
+#[cfg(unix)]
+fn create_user_dir(path: &StdPath, uid: u32) -> io::Result<()> {
+    use ::std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    if let Some(parent) = path.parent() {
+        ::std::fs::create_dir_all(parent)?;
+    }
+    match ::std::fs::DirBuilder::new().mode(0o700).create(path) {
+        Err(err) if err.kind() != io::ErrorKind::AlreadyExists => return Err(err),
+        _ => {}
+    }
+
+    let metadata = ::std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "directory should be owned by and only accessible to the current user",
+        ));
+    }
+    Ok(())
+}
+
+/// Create a directory only accessible by the given user, unsupported on this platform.
+///
+/// # Errors
+/// Always.
+#[cfg(not(unix))]
+fn create_user_dir(_path: &StdPath, _uid: u32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "user directories are only supported on unix",
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use ::std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    use super::*;
+
+    /// Get a path unique to a test in the temp directory.
+    fn test_dir(name: &str) -> PathBuf {
+        ::std::env::temp_dir().join(format!("katalog_lib_ipc_{name}_{}", ::std::process::id()))
+    }
+
+    /// Directory removed when dropped.
+    struct RemoveDir(PathBuf);
+
+    impl Drop for RemoveDir {
+        fn drop(&mut self) {
+            _ = ::std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn user_dir_is_private() {
+        let uid = current_uid().expect("uid should be known on unix");
+        let dir = test_dir("private");
+        _ = ::std::fs::remove_dir_all(&dir);
+
+        create_user_dir(&dir.join("user"), uid).expect("directory should be created");
+        let metadata = ::std::fs::metadata(dir.join("user")).expect("directory should exist");
+        assert_eq!(metadata.uid(), uid);
+        assert_eq!(metadata.mode() & 0o777, 0o700);
+
+        // Existing directories are accepted if private.
+        create_user_dir(&dir.join("user"), uid).expect("directory should be accepted");
+
+        _ = ::std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn user_dir_fails_closed() {
+        let uid = current_uid().expect("uid should be known on unix");
+        let dir = test_dir("fails_closed");
+        _ = ::std::fs::remove_dir_all(&dir);
+
+        let shared = dir.join("shared");
+        ::std::fs::create_dir_all(&shared).expect("directory should be created");
+        ::std::fs::set_permissions(&shared, ::std::fs::Permissions::from_mode(0o755))
+            .expect("permissions should be set");
+        assert!(create_user_dir(&shared, uid).is_err());
+
+        let other = dir.join("other");
+        create_user_dir(&other, uid).expect("directory should be created");
+        assert!(create_user_dir(&other, uid.wrapping_add(1)).is_err());
+
+        let file = dir.join("file");
+        ::std::fs::write(&file, b"").expect("file should be written");
+        assert!(create_user_dir(&file, uid).is_err());
+
+        _ = ::std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn restricted_config_uses_user_dir() {
+        assert!(matches!(AccessControl::Any.config(), Ok(None)));
+
+        let uid = current_uid().expect("uid should be known on unix");
+        let dir = RemoveDir(test_dir("config"));
+        _ = ::std::fs::remove_dir_all(&dir.0);
+
+        let mut base = Config::global_config().clone();
+        base.global
+            .set_root_path(&root_path(&dir.0).expect("temp directory should be a valid path"));
+        let config = AccessControl::SameUser
+            .config_from(&base)
+            .expect("config should be created")
+            .expect("restricted access control should use a custom config");
+        let root = PathBuf::from(config.global.root_path().to_string());
+        assert_eq!(root, dir.0.join(format!("u{uid}")));
+        assert_eq!(
+            ::std::fs::metadata(&root)
+                .expect("root should exist")
+                .mode()
+                & 0o077,
+            0
+        );
+        assert!(
+            config
+                .global
+                .prefix
+                .to_string()
+                .ends_with(&format!("u{uid}_"))
+        );
+    }
+
+    #[test]
+    fn sender_user() {
+        let uid = current_uid();
+        assert!(Sender { pid: 1, uid }.is_current_user());
+        assert!(
+            !Sender {
+                pid: 1,
+                uid: uid.map(|uid| uid.wrapping_add(1))
+            }
+            .is_current_user()
+        );
+        assert!(!Sender { pid: 1, uid: None }.is_current_user());
+    }
+}
@@ -8,6 +8,7 @@ use ::core::{
     time::Duration,
 };
 use ::std::{
+    path::PathBuf,
     sync::{Arc, Weak},
     time::Instant,
 };
@@ -24,12 +25,17 @@ use ::iceoryx2::{
     },
     prelude::*,
     service::{
-        builder::publish_subscribe::PublishSubscribeOpenOrCreateError,
+        builder::publish_subscribe::{
+            PublishSubscribeOpenError, PublishSubscribeOpenOrCreateError,
+        },
         service_name::ServiceNameError,
+        static_config::publish_subscribe::StaticConfig,
     },
 };
 use iceoryx2::service::builder::event::EventOpenOrCreateError;
 
+use crate::{AccessControl, access_control::node_dir};
+
 /// Handle to subscriber thread.
 #[derive(Debug, Clone)]
 pub struct SubscriberHandle {
@@ -97,6 +103,99 @@ impl PartialEq for SubscriberHandle {
 
 impl Eq for SubscriberHandle {}
 
+/// What to do with a message when the subscriber queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowStrategy {
+    /// Block the sending process until the subscriber has made room.
+    Block,
+    /// Drop the oldest message in the queue to make room for the new one.
+    DropOldest,
+    /// Do not deliver the message, sending fails with [PublishError::QueueFull].
+    Reject,
+}
+
+impl OverflowStrategy {
+    /// If the service should use safe overflow for this strategy.
+    const fn safe_overflow(self) -> bool {
+        matches!(self, Self::DropOldest)
+    }
+
+    /// Publisher strategy used when the subscriber cannot receive any more messages.
+    const fn unable_to_deliver_strategy(self) -> UnableToDeliverStrategy {
+        match self {
+            Self::Block | Self::DropOldest => UnableToDeliverStrategy::Block,
+            Self::Reject => UnableToDeliverStrategy::DiscardSample,
+        }
+    }
+}
+
+/// Queue configuration of service, unset values use iceoryx2 defaults.
+#[derive(Debug, Clone, Copy, Default)]
+struct QueueConfig {
+    /// Subscriber buffer size.
+    buffer_size: Option<usize>,
+    /// Amount of messages replayed to late-joining subscribers.
+    history_size: Option<usize>,
+    /// Behaviour when subscriber buffer is full.
+    overflow: Option<OverflowStrategy>,
+}
+
+impl QueueConfig {
+    /// Verify that the static config of a service supports the queue config.
+    ///
+    /// # Errors
+    /// If the service has a smaller buffer or history than requested, or a different
+    /// overflow behaviour.
+    fn validate(&self, static_config: &StaticConfig) -> Result<(), PublishSubscribeOpenError> {
+        self.validate_values(
+            static_config.subscriber_max_buffer_size(),
+            static_config.history_size(),
+            static_config.has_safe_overflow(),
+        )
+    }
+
+    /// Verify that the queue config is supported by a service with the given values.
+    ///
+    /// # Errors
+    /// If the service has a smaller buffer or history than requested, or a different
+    /// overflow behaviour.
+    fn validate_values(
+        &self,
+        buffer_size: usize,
+        history_size: usize,
+        safe_overflow: bool,
+    ) -> Result<(), PublishSubscribeOpenError> {
+        if let Some(expected) = self.buffer_size
+            && buffer_size < expected
+        {
+            ::log::error!(
+                "existing service has a buffer size of {buffer_size}, expected at least {expected}"
+            );
+            return Err(PublishSubscribeOpenError::DoesNotSupportRequestedMinBufferSize);
+        }
+
+        if let Some(expected) = self.history_size
+            && history_size < expected
+        {
+            ::log::error!(
+                "existing service has a history size of {history_size}, expected at least {expected}"
+            );
+            return Err(PublishSubscribeOpenError::DoesNotSupportRequestedMinHistorySize);
+        }
+
+        if let Some(expected) = self.overflow
+            && safe_overflow != expected.safe_overflow()
+        {
+            ::log::error!(
+                "existing service has safe overflow set to {safe_overflow}, expected {expected:?}"
+            );
+            return Err(PublishSubscribeOpenError::IncompatibleOverflowBehavior);
+        }
+
+        Ok(())
+    }
+}
+
 /// Event used for notifying subscriber.
 const NOTIFY_EVENT: EventId = EventId::new(11);
 
@@ -119,55 +218,90 @@ type EventPortFactory =
 type EventService = ::iceoryx2::service::port_factory::event::PortFactory<ipc_threadsafe::Service>;
 
 /// Create ipc node.
-fn build_node(name: &NodeName) -> Result<Node<ipc_threadsafe::Service>, NodeCreationFailure> {
-    NodeBuilder::new()
+fn build_node(
+    name: &NodeName,
+    access_control: &AccessControl,
+) -> Result<Node<ipc_threadsafe::Service>, NodeCreationFailure> {
+    let builder = NodeBuilder::new()
         .name(name)
-        .signal_handling_mode(SignalHandlingMode::Disabled)
-        .create::<ipc_threadsafe::Service>()
+        .signal_handling_mode(SignalHandlingMode::Disabled);
+
+    if let Some(config) = access_control.config()? {
+        builder.config(&config).create::<ipc_threadsafe::Service>()
+    } else {
+        builder.create::<ipc_threadsafe::Service>()
+    }
 }
 
 /// Create publish subscribe service.
 fn build_serice_<M>(
     name: &ServiceName,
     node: &Node<ipc_threadsafe::Service>,
+    queue: &QueueConfig,
 ) -> Result<PublishSubscribePortFactory<M>, PublishSubscribeOpenOrCreateError>
 where
     M: Debug + ZeroCopySend,
 {
-    node.service_builder(name)
+    let mut builder = node
+        .service_builder(name)
         .publish_subscribe::<M>()
-        .max_subscribers(1)
-        .open_or_create()
+        .max_subscribers(1);
+
+    if let Some(buffer_size) = queue.buffer_size {
+        builder = builder.subscriber_max_buffer_size(buffer_size);
+    }
+    if let Some(history_size) = queue.history_size {
+        builder = builder.history_size(history_size);
+    }
+    if let Some(overflow) = queue.overflow {
+        builder = builder.enable_safe_overflow(overflow.safe_overflow());
+    }
+
+    builder.open_or_create()
 }
 
 /// Create publish subscribe service.
 fn build_service<M>(
     name: &ServiceName,
     node: &Node<ipc_threadsafe::Service>,
+    queue: &QueueConfig,
 ) -> Result<PublishSubscribePortFactory<M>, PublishSubscribeOpenOrCreateError>
 where
     M: Debug + ZeroCopySend,
 {
-    build_serice_::<M>(name, node).or_else(|_| {
-        if let Err(err) = Node::<ipc_threadsafe::Service>::list(
-            ::iceoryx2::config::Config::global_config(),
-            |node_state| {
-                if let NodeState::<ipc_threadsafe::Service>::Dead(view) = node_state {
-                    ::log::info!("cleanup of dead node {view:?}");
-                    if let Err(err) = view.remove_stale_resources() {
-                        ::log::warn!("could nod clean up stale resources, {err:?}");
-                    }
+    build_serice_::<M>(name, node, queue).or_else(|_| {
+        if let Err(err) = Node::<ipc_threadsafe::Service>::list(node.config(), |node_state| {
+            if let NodeState::<ipc_threadsafe::Service>::Dead(view) = node_state {
+                ::log::info!("cleanup of dead node {view:?}");
+                if let Err(err) = view.remove_stale_resources() {
+                    ::log::warn!("could nod clean up stale resources, {err:?}");
                 }
-                CallbackProgression::Continue
-            },
-        ) {
+            }
+            CallbackProgression::Continue
+        }) {
             ::log::error!("failed to perform stale resource cleanup, {err}");
         }
 
-        build_serice_(name, node)
+        build_serice_(name, node, queue)
     })
 }
 
+/// Create subscriber using configured buffer size.
+fn build_subscriber<M>(
+    service: &PublishSubscribePortFactory<M>,
+    queue: &QueueConfig,
+) -> Result<Subscriber<ipc_threadsafe::Service, M, ()>, SubscriberCreateError>
+where
+    M: Debug + ZeroCopySend,
+{
+    let builder = service.subscriber_builder();
+    if let Some(buffer_size) = queue.buffer_size {
+        builder.buffer_size(buffer_size).create()
+    } else {
+        builder.create()
+    }
+}
+
 /// Create event service.
 fn build_event_service(
     name: &ServiceName,
@@ -179,9 +313,12 @@ fn build_event_service(
 /// Create subscriber thread.
 fn create_subscriber_thread<M, E, S>(
     subscriber: Subscriber<ipc_threadsafe::Service, M, ()>,
+    service: PublishSubscribePortFactory<M>,
     event_service: EventService,
     thread_name: String,
     mut receive: S,
+    mut access_control: AccessControl,
+    node_dir: PathBuf,
 ) -> Result<SubscriberHandle, E>
 where
     M: Debug + ZeroCopySend,
@@ -215,6 +352,14 @@ where
                 {
                     while let Some(message) = subscriber.receive()? {
                         ::log::info!("received ipc message");
+                        if !access_control.authorize(
+                            service.dynamic_config(),
+                            message.origin(),
+                            &node_dir,
+                        ) {
+                            ::log::warn!("rejected ipc message from unauthorized sender");
+                            continue;
+                        }
                         receive(&message)?;
                     }
                 }
@@ -233,23 +378,39 @@ where
         .map_err(E::from)
 }
 
+/// Error returned when a message could not be published.
+#[derive(Debug, ::thiserror::Error)]
+pub enum PublishError {
+    /// Subscriber queue is full and the overflow strategy is [OverflowStrategy::Reject].
+    #[error("message rejected, subscriber queue is full")]
+    QueueFull,
+}
+
 /// Publish input to eventual subscribers.
 fn publish_input<M, I, E>(
     node: Node<ipc_threadsafe::Service>,
     service: PublishSubscribePortFactory<M>,
     event_service: EventService,
     input: I,
+    overflow: Option<OverflowStrategy>,
 ) -> Result<(), E>
 where
     M: 'static + Debug + ZeroCopySend,
     E: From<SendError>
-        + From<RequestSendError>
+        + From<PublishError>
         + From<PublisherCreateError>
         + From<NotifierCreateError>
         + From<LoanError>,
     I: FnOnce() -> Result<M, E>,
 {
-    let publisher = service.publisher_builder().create()?;
+    let publisher = if let Some(overflow) = overflow {
+        service
+            .publisher_builder()
+            .unable_to_deliver_strategy(overflow.unable_to_deliver_strategy())
+            .create()?
+    } else {
+        service.publisher_builder().create()?
+    };
     let notifier = event_service
         .notifier_builder()
         .default_event_id(NOTIFY_EVENT)
@@ -257,7 +418,10 @@ where
 
     let message = publisher.loan_uninit()?;
     let message = message.write_payload(input()?);
-    message.send()?;
+    if message.send()? == 0 && overflow == Some(OverflowStrategy::Reject) {
+        ::log::warn!("message rejected, subscriber queue is full");
+        return Err(PublishError::QueueFull.into());
+    }
     ::log::info!("sent ipc message");
     let wait_result = if let Err(err) = notifier.notify() {
         ::log::error!("could not send notification event, {err}");
@@ -290,6 +454,8 @@ fn subscribe_only_<M, R, T, E>(
     thread_name: T,
     receive: R,
     timeout: Duration,
+    queue: QueueConfig,
+    access_control: AccessControl,
 ) -> Result<SubscriberHandle, E>
 where
     M: 'static + Debug + ZeroCopySend,
@@ -316,14 +482,23 @@ where
     let node_name = NodeName::new(node_name)?;
     let service_name = ServiceName::new(service_name)?;
 
-    let node = build_node(&node_name)?;
-    let service = build_service::<M>(&service_name, &node)?;
+    let node = build_node(&node_name, &access_control)?;
+    let service = build_service::<M>(&service_name, &node, &queue)?;
+    queue
+        .validate(service.static_config())
+        .map_err(PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError)?;
     let event_service = build_event_service(&service_name, &node)?;
 
-    match service.subscriber_builder().create() {
-        Ok(subscriber) => {
-            create_subscriber_thread(subscriber, event_service, thread_name(), receive)
-        }
+    match build_subscriber(&service, &queue) {
+        Ok(subscriber) => create_subscriber_thread(
+            subscriber,
+            service,
+            event_service,
+            thread_name(),
+            receive,
+            access_control,
+            node_dir(node.config()),
+        ),
         Err(SubscriberCreateError::ExceedsMaxSupportedSubscribers) => {
             let timeout_instant = Instant::now() + timeout;
             let mut max_sleep = 0.002f64;
@@ -338,10 +513,16 @@ where
                     0.0..=max_sleep,
                 )))?;
 
-                return match service.subscriber_builder().create() {
-                    Ok(subscriber) => {
-                        create_subscriber_thread(subscriber, event_service, thread_name(), receive)
-                    }
+                return match build_subscriber(&service, &queue) {
+                    Ok(subscriber) => create_subscriber_thread(
+                        subscriber,
+                        service,
+                        event_service,
+                        thread_name(),
+                        receive,
+                        access_control,
+                        node_dir(node.config()),
+                    ),
                     Err(SubscriberCreateError::ExceedsMaxSupportedSubscribers) => {
                         if Instant::now() > timeout_instant {
                             return Err(SubscribeOnlyTimeoutError { timeout }.into());
@@ -363,6 +544,8 @@ fn single_process_<M, I, R, T, E>(
     thread_name: T,
     input: I,
     receive: R,
+    queue: QueueConfig,
+    access_control: AccessControl,
 ) -> Result<ControlFlow<(), SubscriberHandle>, E>
 where
     M: 'static + Debug + ZeroCopySend,
@@ -379,6 +562,7 @@ where
         + From<LoanError>
         + From<NodeCreationFailure>
         + From<NotifierCreateError>
+        + From<PublishError>
         + From<PublishSubscribeOpenOrCreateError>
         + From<PublisherCreateError>
         + From<ReceiveError>
@@ -391,17 +575,26 @@ where
     let node_name = NodeName::new(node_name)?;
     let service_name = ServiceName::new(service_name)?;
 
-    let node = build_node(&node_name)?;
-    let service = build_service::<M>(&service_name, &node)?;
+    let node = build_node(&node_name, &access_control)?;
+    let service = build_service::<M>(&service_name, &node, &queue)?;
+    queue
+        .validate(service.static_config())
+        .map_err(PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError)?;
     let event_service = build_event_service(&service_name, &node)?;
 
-    match service.subscriber_builder().create() {
-        Ok(subscriber) => {
-            create_subscriber_thread(subscriber, event_service, thread_name(), receive)
-                .map(ControlFlow::Continue)
-        }
+    match build_subscriber(&service, &queue) {
+        Ok(subscriber) => create_subscriber_thread(
+            subscriber,
+            service,
+            event_service,
+            thread_name(),
+            receive,
+            access_control,
+            node_dir(node.config()),
+        )
+        .map(ControlFlow::Continue),
         Err(SubscriberCreateError::ExceedsMaxSupportedSubscribers) => {
-            publish_input::<M, I, E>(node, service, event_service, input)?;
+            publish_input::<M, I, E>(node, service, event_service, input, queue.overflow)?;
             Ok(ControlFlow::Break(()))
         }
         Err(err) => Err(err.into()),
@@ -428,6 +621,15 @@ pub fn subscribe_only<M, R, T, E>(
     /// For how long to attempt to replace other subscribers.
     #[builder(default = Duration::from_millis(200))]
     timeout: Duration,
+    /// Size of the subscriber message queue.
+    buffer_size: Option<usize>,
+    /// Amount of recent messages replayed to late-joining subscribers.
+    history_size: Option<usize>,
+    /// What to do with messages sent when the subscriber queue is full.
+    overflow: Option<OverflowStrategy>,
+    /// Restrict which processes messages are received from.
+    #[builder(default)]
+    access_control: AccessControl,
 ) -> Result<SubscriberHandle, E>
 where
     M: 'static + Debug + ZeroCopySend,
@@ -463,6 +665,12 @@ where
         },
         receive,
         timeout,
+        QueueConfig {
+            buffer_size,
+            history_size,
+            overflow,
+        },
+        access_control,
     )
 }
 
@@ -485,6 +693,15 @@ pub fn single_process<M, I, R, T, E>(
     input: I,
     /// Recevier for inputs sent from other processes if subscriber.
     receive: R,
+    /// Size of the subscriber message queue.
+    buffer_size: Option<usize>,
+    /// Amount of recent messages replayed to late-joining subscribers.
+    history_size: Option<usize>,
+    /// What to do with messages sent when the subscriber queue is full.
+    overflow: Option<OverflowStrategy>,
+    /// Restrict which processes messages are received from.
+    #[builder(default)]
+    access_control: AccessControl,
 ) -> Result<ControlFlow<(), SubscriberHandle>, E>
 where
     M: 'static + Debug + ZeroCopySend,
@@ -501,6 +718,7 @@ where
         + From<LoanError>
         + From<NodeCreationFailure>
         + From<NotifierCreateError>
+        + From<PublishError>
         + From<PublishSubscribeOpenOrCreateError>
         + From<PublisherCreateError>
         + From<ReceiveError>
@@ -522,5 +740,162 @@ where
         },
         input,
         receive,
+        QueueConfig {
+            buffer_size,
+            history_size,
+            overflow,
+        },
+        access_control,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a node and a service name unique to a test.
+    fn test_service(name: &str) -> (Node<ipc_threadsafe::Service>, ServiceName) {
+        let node_name =
+            NodeName::new(&format!("katalog_lib_ipc_{name}")).expect("node name should be valid");
+        let node = build_node(&node_name, &AccessControl::Any).expect("node should be created");
+        let service_name = ServiceName::new(&format!(
+            "katalog_lib_ipc_test_{name}_{}",
+            ::std::process::id()
+        ))
+        .expect("service name should be valid");
+        (node, service_name)
+    }
+
+    #[test]
+    fn queue_config_validate() {
+        let queue = QueueConfig {
+            buffer_size: Some(4),
+            history_size: Some(1),
+            overflow: Some(OverflowStrategy::DropOldest),
+        };
+
+        assert_eq!(queue.validate_values(4, 1, true), Ok(()));
+        assert_eq!(queue.validate_values(8, 2, true), Ok(()));
+        assert_eq!(
+            queue.validate_values(2, 1, true),
+            Err(PublishSubscribeOpenError::DoesNotSupportRequestedMinBufferSize)
+        );
+        assert_eq!(
+            queue.validate_values(8, 0, true),
+            Err(PublishSubscribeOpenError::DoesNotSupportRequestedMinHistorySize)
+        );
+        assert_eq!(
+            queue.validate_values(8, 2, false),
+            Err(PublishSubscribeOpenError::IncompatibleOverflowBehavior)
+        );
+        assert_eq!(QueueConfig::default().validate_values(1, 0, false), Ok(()));
+    }
+
+    #[test]
+    fn queue_config_mismatch() {
+        let (node, name) = test_service("mismatch");
+        let created = QueueConfig {
+            buffer_size: Some(4),
+            history_size: Some(0),
+            overflow: Some(OverflowStrategy::Reject),
+        };
+        let service =
+            build_service::<u64>(&name, &node, &created).expect("service should be created");
+        assert_eq!(created.validate(service.static_config()), Ok(()));
+
+        let smaller = QueueConfig {
+            buffer_size: Some(2),
+            ..created
+        };
+        let opened =
+            build_service::<u64>(&name, &node, &smaller).expect("service should be opened");
+        assert_eq!(smaller.validate(opened.static_config()), Ok(()));
+
+        let larger = QueueConfig {
+            buffer_size: Some(8),
+            ..created
+        };
+        assert!(matches!(
+            build_service::<u64>(&name, &node, &larger),
+            Err(
+                PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError(
+                    PublishSubscribeOpenError::DoesNotSupportRequestedMinBufferSize
+                )
+            )
+        ));
+
+        let overflow = QueueConfig {
+            overflow: Some(OverflowStrategy::DropOldest),
+            ..created
+        };
+        assert!(matches!(
+            build_service::<u64>(&name, &node, &overflow),
+            Err(
+                PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError(
+                    PublishSubscribeOpenError::IncompatibleOverflowBehavior
+                )
+            )
+        ));
+    }
+
+    #[test]
+    fn overflow_drop_oldest() {
+        let (node, name) = test_service("drop_oldest");
+        let queue = QueueConfig {
+            buffer_size: Some(1),
+            history_size: Some(0),
+            overflow: Some(OverflowStrategy::DropOldest),
+        };
+        let service =
+            build_service::<u64>(&name, &node, &queue).expect("service should be created");
+        let subscriber = build_subscriber(&service, &queue).expect("subscriber should be created");
+        let publisher = service
+            .publisher_builder()
+            .unable_to_deliver_strategy(OverflowStrategy::DropOldest.unable_to_deliver_strategy())
+            .create()
+            .expect("publisher should be created");
+
+        assert_eq!(publisher.send_copy(1), Ok(1));
+        assert_eq!(publisher.send_copy(2), Ok(1));
+
+        let sample = subscriber
+            .receive()
+            .expect("receive should succeed")
+            .expect("a message should be queued");
+        assert_eq!(*sample, 2);
+        drop(sample);
+        assert!(
+            subscriber
+                .receive()
+                .expect("receive should succeed")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn overflow_reject() {
+        let (node, name) = test_service("reject");
+        let queue = QueueConfig {
+            buffer_size: Some(1),
+            history_size: Some(0),
+            overflow: Some(OverflowStrategy::Reject),
+        };
+        let service =
+            build_service::<u64>(&name, &node, &queue).expect("service should be created");
+        let subscriber = build_subscriber(&service, &queue).expect("subscriber should be created");
+        let publisher = service
+            .publisher_builder()
+            .unable_to_deliver_strategy(OverflowStrategy::Reject.unable_to_deliver_strategy())
+            .create()
+            .expect("publisher should be created");
+
+        assert_eq!(publisher.send_copy(1), Ok(1));
+        assert_eq!(publisher.send_copy(2), Ok(0));
+
+        let sample = subscriber
+            .receive()
+            .expect("receive should succeed")
+            .expect("a message should be queued");
+        assert_eq!(*sample, 1);
+    }
+}
@@ -0,0 +1,163 @@
+//! Benchmark of the derived `FromStr` implementation using case conversion.
+//!
+//! Compares `case_convert`, which groups the spellings of every variant into a single match
+//! arm, with the expansion of the derive before this, which generated a match arm per
+//! spelling. Parse time is measured for different kinds of input, and code size as the
+//! length of the generated code.
+//!
+//! Run using `cargo bench -p katalog-lib-reflect`.
+
+use ::core::{hint::black_box, time::Duration};
+use ::std::time::Instant;
+
+use ::katalog_lib_reflect::{FromStr, ParseVariant};
+use ::proc_macro2::TokenStream;
+
+/// Generate an enum with many variants using given attributes, and optionally a constant
+/// with the source of the enum.
+macro_rules! column_enum {
+    ($name:ident $(, $source:ident)?; $($attr:tt)*) => {
+        column_enum!(@ $name, $($source,)? [$($attr)*], {
+            FileName,
+            FileSize,
+            FileType,
+            ModifiedTime,
+            CreatedTime,
+            AccessedTime,
+            OwnerName,
+            GroupName,
+            Permissions,
+            LinkTarget,
+            MimeType,
+            ParentDirectory,
+            FullPath,
+            Extension,
+            IsHidden,
+            IsSymlink,
+            IsDirectory,
+            ChildCount,
+            DiskUsage,
+            InodeNumber,
+            DeviceId,
+            HardLinks,
+            BlockSize,
+            BlockCount,
+        });
+    };
+    (@ $name:ident, $source:ident, [$($attr:tt)*], $body:tt) => {
+        column_enum!(@ $name, [$($attr)*], $body);
+
+        /// Source of enum, used to measure size of generated code.
+        const $source: &str = stringify!($($attr)* enum $name $body);
+    };
+    (@ $name:ident, [$($attr:tt)*], $body:tt) => {
+        /// Enum with many variants for benchmarking.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $($attr)*
+        enum $name $body
+    };
+}
+
+column_enum!(Column, COLUMN_SOURCE; #[derive(FromStr)] #[reflect(case_convert)]);
+column_enum!(Baseline;);
+
+/// `ParseVariant` implementation of `Baseline`, expanded from the derive before spellings
+/// were grouped by variant.
+#[path = "from_str/baseline.rs"]
+mod baseline;
+
+/// Source of `baseline` module, used to measure size of generated code.
+const BASELINE_IMPL: &str = include_str!("from_str/baseline.rs");
+
+/// Inputs to parse, grouped by kind of input.
+const INPUTS: &[(&str, &[&str])] = &[
+    (
+        "canonical",
+        &["FileName", "ParentDirectory", "BlockCount", "IsHidden"],
+    ),
+    (
+        "converted",
+        &[
+            "file_name",
+            "MODIFIED-TIME",
+            "parentDirectory",
+            "Block Count",
+            "INODE_NUMBER",
+        ],
+    ),
+    (
+        "unknown",
+        &["not-a-column", "x", "FileNameX", "block_sizes"],
+    ),
+];
+
+/// Amount of times every input is parsed.
+const ITERATIONS: u32 = 200_000;
+
+/// Time parsing of inputs as `T`, returning time per parse.
+fn bench<T: ParseVariant>(inputs: &[&str]) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for input in inputs {
+            _ = black_box(T::parse_variant(black_box(input)));
+        }
+    }
+    start.elapsed() / (ITERATIONS * inputs.len() as u32)
+}
+
+/// Get length of code generated by the `FromStr` derive for an enum source.
+fn generated_len(source: &str) -> usize {
+    let tokens = source
+        .parse::<TokenStream>()
+        .expect("enum source should be valid tokens");
+    ::katalog_lib_reflect_derive_lib::derive_from_str(tokens)
+        .to_string()
+        .len()
+}
+
+/// Get length of code in the same format as [generated_len].
+fn source_len(source: &str) -> usize {
+    source
+        .parse::<TokenStream>()
+        .expect("source should be valid tokens")
+        .to_string()
+        .len()
+}
+
+/// Check that both enums accept the same spellings, and get the amount of spellings.
+fn check_spellings() -> usize {
+    let mut spellings = 0;
+    for line in BASELINE_IMPL.lines() {
+        let Some((spelling, variant)) = line.trim().split_once(" => Some(Self::") else {
+            continue;
+        };
+        let spelling = spelling.trim_matches('"');
+        let variant = variant.trim_end_matches("),");
+        let column = Column::parse_variant(spelling);
+        assert_eq!(
+            column.map(|column| format!("{column:?}")).as_deref(),
+            Some(variant),
+            "{spelling:?} should parse the same as the baseline",
+        );
+        spellings += 1;
+    }
+    spellings
+}
+
+#[expect(clippy::print_stdout, reason = "benchmark results are printed")]
+fn main() {
+    println!("{:<10} {} spellings", "accepted", check_spellings());
+    println!(
+        "{:<10} case_convert {:>6}, baseline {:>6}",
+        "code size",
+        generated_len(COLUMN_SOURCE),
+        source_len(BASELINE_IMPL),
+    );
+    for (kind, inputs) in INPUTS {
+        println!(
+            "{kind:<10} case_convert {:>6?}, baseline {:>6?}",
+            bench::<Column>(inputs),
+            bench::<Baseline>(inputs),
+        );
+    }
+}
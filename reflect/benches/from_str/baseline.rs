@@ -0,0 +1,353 @@
+use ::katalog_lib_reflect::ParseVariant;
+
+use super::Baseline;
+
+impl ParseVariant for Baseline {
+    const TYPE_NAME: &'static str = "Baseline";
+    const NAMES: &'static [&'static str] = &[
+        "FileName",
+        "FileSize",
+        "FileType",
+        "ModifiedTime",
+        "CreatedTime",
+        "AccessedTime",
+        "OwnerName",
+        "GroupName",
+        "Permissions",
+        "LinkTarget",
+        "MimeType",
+        "ParentDirectory",
+        "FullPath",
+        "Extension",
+        "IsHidden",
+        "IsSymlink",
+        "IsDirectory",
+        "ChildCount",
+        "DiskUsage",
+        "InodeNumber",
+        "DeviceId",
+        "HardLinks",
+        "BlockSize",
+        "BlockCount",
+    ];
+
+    fn parse_variant(s: &str) -> Option<Self> {
+        match s {
+            "file_type" => Some(Self::FileType),
+            "parent_directory" => Some(Self::ParentDirectory),
+            "HARD_LINKS" => Some(Self::HardLinks),
+            "FileSize" => Some(Self::FileSize),
+            "mimeType" => Some(Self::MimeType),
+            "created time" => Some(Self::CreatedTime),
+            "full_path" => Some(Self::FullPath),
+            "LINK_TARGET" => Some(Self::LinkTarget),
+            "device id" => Some(Self::DeviceId),
+            "Accessed-Time" => Some(Self::AccessedTime),
+            "MIMETYPE" => Some(Self::MimeType),
+            "Is-Directory" => Some(Self::IsDirectory),
+            "File-Size" => Some(Self::FileSize),
+            "FileName" => Some(Self::FileName),
+            "DISK USAGE" => Some(Self::DiskUsage),
+            "accessed_time" => Some(Self::AccessedTime),
+            "child count" => Some(Self::ChildCount),
+            "block count" => Some(Self::BlockCount),
+            "DISKUSAGE" => Some(Self::DiskUsage),
+            "Device_Id" => Some(Self::DeviceId),
+            "groupname" => Some(Self::GroupName),
+            "ACCESSEDTIME" => Some(Self::AccessedTime),
+            "PARENT_DIRECTORY" => Some(Self::ParentDirectory),
+            "ISSYMLINK" => Some(Self::IsSymlink),
+            "Group Name" => Some(Self::GroupName),
+            "OWNER-NAME" => Some(Self::OwnerName),
+            "File Size" => Some(Self::FileSize),
+            "mime_type" => Some(Self::MimeType),
+            "Parent directory" => Some(Self::ParentDirectory),
+            "Block Count" => Some(Self::BlockCount),
+            "link-target" => Some(Self::LinkTarget),
+            "Block size" => Some(Self::BlockSize),
+            "Parent_Directory" => Some(Self::ParentDirectory),
+            "hard links" => Some(Self::HardLinks),
+            "PERMISSIONS" => Some(Self::Permissions),
+            "BLOCK-SIZE" => Some(Self::BlockSize),
+            "Block_Size" => Some(Self::BlockSize),
+            "owner-name" => Some(Self::OwnerName),
+            "Child-Count" => Some(Self::ChildCount),
+            "FULLPATH" => Some(Self::FullPath),
+            "Hard-Links" => Some(Self::HardLinks),
+            "LINK-TARGET" => Some(Self::LinkTarget),
+            "DiskUsage" => Some(Self::DiskUsage),
+            "LINKTARGET" => Some(Self::LinkTarget),
+            "MODIFIED TIME" => Some(Self::ModifiedTime),
+            "GROUP NAME" => Some(Self::GroupName),
+            "GROUP_NAME" => Some(Self::GroupName),
+            "File_Type" => Some(Self::FileType),
+            "Is_Directory" => Some(Self::IsDirectory),
+            "Accessed Time" => Some(Self::AccessedTime),
+            "BLOCK_COUNT" => Some(Self::BlockCount),
+            "issymlink" => Some(Self::IsSymlink),
+            "modifiedTime" => Some(Self::ModifiedTime),
+            "Accessed time" => Some(Self::AccessedTime),
+            "Disk_Usage" => Some(Self::DiskUsage),
+            "CHILDCOUNT" => Some(Self::ChildCount),
+            "is directory" => Some(Self::IsDirectory),
+            "full-path" => Some(Self::FullPath),
+            "ownername" => Some(Self::OwnerName),
+            "BLOCK_SIZE" => Some(Self::BlockSize),
+            "ISHIDDEN" => Some(Self::IsHidden),
+            "MimeType" => Some(Self::MimeType),
+            "DEVICE ID" => Some(Self::DeviceId),
+            "Inode Number" => Some(Self::InodeNumber),
+            "child_count" => Some(Self::ChildCount),
+            "isHidden" => Some(Self::IsHidden),
+            "Full path" => Some(Self::FullPath),
+            "Block-Size" => Some(Self::BlockSize),
+            "Disk-Usage" => Some(Self::DiskUsage),
+            "DISK_USAGE" => Some(Self::DiskUsage),
+            "Inode-Number" => Some(Self::InodeNumber),
+            "EXTENSION" => Some(Self::Extension),
+            "Modified Time" => Some(Self::ModifiedTime),
+            "Disk Usage" => Some(Self::DiskUsage),
+            "Hard Links" => Some(Self::HardLinks),
+            "AccessedTime" => Some(Self::AccessedTime),
+            "FILE-SIZE" => Some(Self::FileSize),
+            "IS HIDDEN" => Some(Self::IsHidden),
+            "block size" => Some(Self::BlockSize),
+            "Mime_Type" => Some(Self::MimeType),
+            "Group_Name" => Some(Self::GroupName),
+            "link_target" => Some(Self::LinkTarget),
+            "ACCESSED-TIME" => Some(Self::AccessedTime),
+            "CHILD COUNT" => Some(Self::ChildCount),
+            "Device id" => Some(Self::DeviceId),
+            "createdTime" => Some(Self::CreatedTime),
+            "owner_name" => Some(Self::OwnerName),
+            "FILE-TYPE" => Some(Self::FileType),
+            "Is directory" => Some(Self::IsDirectory),
+            "blockcount" => Some(Self::BlockCount),
+            "file name" => Some(Self::FileName),
+            "FILESIZE" => Some(Self::FileSize),
+            "OWNER NAME" => Some(Self::OwnerName),
+            "Modified_Time" => Some(Self::ModifiedTime),
+            "File name" => Some(Self::FileName),
+            "inode_number" => Some(Self::InodeNumber),
+            "ownerName" => Some(Self::OwnerName),
+            "FullPath" => Some(Self::FullPath),
+            "Hard links" => Some(Self::HardLinks),
+            "File_Name" => Some(Self::FileName),
+            "filetype" => Some(Self::FileType),
+            "child-count" => Some(Self::ChildCount),
+            "childCount" => Some(Self::ChildCount),
+            "is-directory" => Some(Self::IsDirectory),
+            "File-Type" => Some(Self::FileType),
+            "Device-Id" => Some(Self::DeviceId),
+            "createdtime" => Some(Self::CreatedTime),
+            "GROUPNAME" => Some(Self::GroupName),
+            "fullPath" => Some(Self::FullPath),
+            "FILE_NAME" => Some(Self::FileName),
+            "diskusage" => Some(Self::DiskUsage),
+            "permissions" => Some(Self::Permissions),
+            "FILETYPE" => Some(Self::FileType),
+            "Extension" => Some(Self::Extension),
+            "file_name" => Some(Self::FileName),
+            "HARDLINKS" => Some(Self::HardLinks),
+            "ParentDirectory" => Some(Self::ParentDirectory),
+            "linkTarget" => Some(Self::LinkTarget),
+            "MIME-TYPE" => Some(Self::MimeType),
+            "Created time" => Some(Self::CreatedTime),
+            "isSymlink" => Some(Self::IsSymlink),
+            "Full Path" => Some(Self::FullPath),
+            "Owner-Name" => Some(Self::OwnerName),
+            "GroupName" => Some(Self::GroupName),
+            "block_count" => Some(Self::BlockCount),
+            "DEVICE_ID" => Some(Self::DeviceId),
+            "Inode number" => Some(Self::InodeNumber),
+            "FILE_TYPE" => Some(Self::FileType),
+            "File_Size" => Some(Self::FileSize),
+            "Permissions" => Some(Self::Permissions),
+            "modifiedtime" => Some(Self::ModifiedTime),
+            "CreatedTime" => Some(Self::CreatedTime),
+            "PARENTDIRECTORY" => Some(Self::ParentDirectory),
+            "ACCESSED TIME" => Some(Self::AccessedTime),
+            "PARENT DIRECTORY" => Some(Self::ParentDirectory),
+            "File Name" => Some(Self::FileName),
+            "Group name" => Some(Self::GroupName),
+            "Block count" => Some(Self::BlockCount),
+            "Block-Count" => Some(Self::BlockCount),
+            "IS_DIRECTORY" => Some(Self::IsDirectory),
+            "InodeNumber" => Some(Self::InodeNumber),
+            "mimetype" => Some(Self::MimeType),
+            "OWNER_NAME" => Some(Self::OwnerName),
+            "INODE NUMBER" => Some(Self::InodeNumber),
+            "mime type" => Some(Self::MimeType),
+            "Created Time" => Some(Self::CreatedTime),
+            "FileType" => Some(Self::FileType),
+            "Link-Target" => Some(Self::LinkTarget),
+            "LinkTarget" => Some(Self::LinkTarget),
+            "BLOCK-COUNT" => Some(Self::BlockCount),
+            "Block_Count" => Some(Self::BlockCount),
+            "GROUP-NAME" => Some(Self::GroupName),
+            "IS-HIDDEN" => Some(Self::IsHidden),
+            "deviceid" => Some(Self::DeviceId),
+            "BLOCK COUNT" => Some(Self::BlockCount),
+            "Created-Time" => Some(Self::CreatedTime),
+            "MODIFIED_TIME" => Some(Self::ModifiedTime),
+            "MIME TYPE" => Some(Self::MimeType),
+            "groupName" => Some(Self::GroupName),
+            "FULL_PATH" => Some(Self::FullPath),
+            "Hard_Links" => Some(Self::HardLinks),
+            "CREATED-TIME" => Some(Self::CreatedTime),
+            "file_size" => Some(Self::FileSize),
+            "MIME_TYPE" => Some(Self::MimeType),
+            "Child_Count" => Some(Self::ChildCount),
+            "is-symlink" => Some(Self::IsSymlink),
+            "FULL PATH" => Some(Self::FullPath),
+            "Link Target" => Some(Self::LinkTarget),
+            "Mime Type" => Some(Self::MimeType),
+            "hard_links" => Some(Self::HardLinks),
+            "PARENT-DIRECTORY" => Some(Self::ParentDirectory),
+            "IsHidden" => Some(Self::IsHidden),
+            "HARD-LINKS" => Some(Self::HardLinks),
+            "file-type" => Some(Self::FileType),
+            "Owner_Name" => Some(Self::OwnerName),
+            "block-size" => Some(Self::BlockSize),
+            "blockCount" => Some(Self::BlockCount),
+            "modified time" => Some(Self::ModifiedTime),
+            "mime-type" => Some(Self::MimeType),
+            "Group-Name" => Some(Self::GroupName),
+            "LINK TARGET" => Some(Self::LinkTarget),
+            "file type" => Some(Self::FileType),
+            "File Type" => Some(Self::FileType),
+            "File type" => Some(Self::FileType),
+            "OwnerName" => Some(Self::OwnerName),
+            "Link_Target" => Some(Self::LinkTarget),
+            "ChildCount" => Some(Self::ChildCount),
+            "BLOCKSIZE" => Some(Self::BlockSize),
+            "BlockSize" => Some(Self::BlockSize),
+            "parent-directory" => Some(Self::ParentDirectory),
+            "accessed-time" => Some(Self::AccessedTime),
+            "Link target" => Some(Self::LinkTarget),
+            "inode-number" => Some(Self::InodeNumber),
+            "Modified-Time" => Some(Self::ModifiedTime),
+            "CREATED_TIME" => Some(Self::CreatedTime),
+            "hard-links" => Some(Self::HardLinks),
+            "MODIFIEDTIME" => Some(Self::ModifiedTime),
+            "full path" => Some(Self::FullPath),
+            "Is Symlink" => Some(Self::IsSymlink),
+            "Is hidden" => Some(Self::IsHidden),
+            "FILE SIZE" => Some(Self::FileSize),
+            "MODIFIED-TIME" => Some(Self::ModifiedTime),
+            "Mime-Type" => Some(Self::MimeType),
+            "accessedTime" => Some(Self::AccessedTime),
+            "FILE_SIZE" => Some(Self::FileSize),
+            "IsDirectory" => Some(Self::IsDirectory),
+            "DEVICEID" => Some(Self::DeviceId),
+            "Parent-Directory" => Some(Self::ParentDirectory),
+            "parentDirectory" => Some(Self::ParentDirectory),
+            "inode number" => Some(Self::InodeNumber),
+            "device-id" => Some(Self::DeviceId),
+            "Parent Directory" => Some(Self::ParentDirectory),
+            "BlockCount" => Some(Self::BlockCount),
+            "FILE NAME" => Some(Self::FileName),
+            "accessed time" => Some(Self::AccessedTime),
+            "extension" => Some(Self::Extension),
+            "IS-SYMLINK" => Some(Self::IsSymlink),
+            "IS DIRECTORY" => Some(Self::IsDirectory),
+            "Owner name" => Some(Self::OwnerName),
+            "group name" => Some(Self::GroupName),
+            "Is Hidden" => Some(Self::IsHidden),
+            "DEVICE-ID" => Some(Self::DeviceId),
+            "diskUsage" => Some(Self::DiskUsage),
+            "isDirectory" => Some(Self::IsDirectory),
+            "ishidden" => Some(Self::IsHidden),
+            "deviceId" => Some(Self::DeviceId),
+            "parent directory" => Some(Self::ParentDirectory),
+            "ISDIRECTORY" => Some(Self::IsDirectory),
+            "disk usage" => Some(Self::DiskUsage),
+            "DISK-USAGE" => Some(Self::DiskUsage),
+            "file size" => Some(Self::FileSize),
+            "ACCESSED_TIME" => Some(Self::AccessedTime),
+            "Owner Name" => Some(Self::OwnerName),
+            "Block Size" => Some(Self::BlockSize),
+            "Modified time" => Some(Self::ModifiedTime),
+            "hardLinks" => Some(Self::HardLinks),
+            "IS_SYMLINK" => Some(Self::IsSymlink),
+            "CHILD_COUNT" => Some(Self::ChildCount),
+            "isdirectory" => Some(Self::IsDirectory),
+            "accessedtime" => Some(Self::AccessedTime),
+            "is symlink" => Some(Self::IsSymlink),
+            "inodenumber" => Some(Self::InodeNumber),
+            "ModifiedTime" => Some(Self::ModifiedTime),
+            "File size" => Some(Self::FileSize),
+            "parentdirectory" => Some(Self::ParentDirectory),
+            "Created_Time" => Some(Self::CreatedTime),
+            "Mime type" => Some(Self::MimeType),
+            "fullpath" => Some(Self::FullPath),
+            "modified_time" => Some(Self::ModifiedTime),
+            "INODE_NUMBER" => Some(Self::InodeNumber),
+            "disk-usage" => Some(Self::DiskUsage),
+            "CREATED TIME" => Some(Self::CreatedTime),
+            "block-count" => Some(Self::BlockCount),
+            "fileName" => Some(Self::FileName),
+            "INODE-NUMBER" => Some(Self::InodeNumber),
+            "group-name" => Some(Self::GroupName),
+            "disk_usage" => Some(Self::DiskUsage),
+            "File-Name" => Some(Self::FileName),
+            "Full_Path" => Some(Self::FullPath),
+            "IsSymlink" => Some(Self::IsSymlink),
+            "fileType" => Some(Self::FileType),
+            "FILE TYPE" => Some(Self::FileType),
+            "Is symlink" => Some(Self::IsSymlink),
+            "Child count" => Some(Self::ChildCount),
+            "FULL-PATH" => Some(Self::FullPath),
+            "HARD LINKS" => Some(Self::HardLinks),
+            "Device Id" => Some(Self::DeviceId),
+            "is_symlink" => Some(Self::IsSymlink),
+            "Is_Hidden" => Some(Self::IsHidden),
+            "FILENAME" => Some(Self::FileName),
+            "IS-DIRECTORY" => Some(Self::IsDirectory),
+            "filesize" => Some(Self::FileSize),
+            "inodeNumber" => Some(Self::InodeNumber),
+            "owner name" => Some(Self::OwnerName),
+            "blockSize" => Some(Self::BlockSize),
+            "FILE-NAME" => Some(Self::FileName),
+            "blocksize" => Some(Self::BlockSize),
+            "Is Directory" => Some(Self::IsDirectory),
+            "HardLinks" => Some(Self::HardLinks),
+            "is_directory" => Some(Self::IsDirectory),
+            "Child Count" => Some(Self::ChildCount),
+            "device_id" => Some(Self::DeviceId),
+            "created-time" => Some(Self::CreatedTime),
+            "is-hidden" => Some(Self::IsHidden),
+            "Is-Symlink" => Some(Self::IsSymlink),
+            "BLOCKCOUNT" => Some(Self::BlockCount),
+            "BLOCK SIZE" => Some(Self::BlockSize),
+            "file-size" => Some(Self::FileSize),
+            "created_time" => Some(Self::CreatedTime),
+            "INODENUMBER" => Some(Self::InodeNumber),
+            "fileSize" => Some(Self::FileSize),
+            "Accessed_Time" => Some(Self::AccessedTime),
+            "Disk usage" => Some(Self::DiskUsage),
+            "link target" => Some(Self::LinkTarget),
+            "filename" => Some(Self::FileName),
+            "CHILD-COUNT" => Some(Self::ChildCount),
+            "CREATEDTIME" => Some(Self::CreatedTime),
+            "is hidden" => Some(Self::IsHidden),
+            "childcount" => Some(Self::ChildCount),
+            "is_hidden" => Some(Self::IsHidden),
+            "Inode_Number" => Some(Self::InodeNumber),
+            "modified-time" => Some(Self::ModifiedTime),
+            "IS SYMLINK" => Some(Self::IsSymlink),
+            "group_name" => Some(Self::GroupName),
+            "hardlinks" => Some(Self::HardLinks),
+            "Is_Symlink" => Some(Self::IsSymlink),
+            "Is-Hidden" => Some(Self::IsHidden),
+            "IS_HIDDEN" => Some(Self::IsHidden),
+            "file-name" => Some(Self::FileName),
+            "linktarget" => Some(Self::LinkTarget),
+            "OWNERNAME" => Some(Self::OwnerName),
+            "DeviceId" => Some(Self::DeviceId),
+            "Full-Path" => Some(Self::FullPath),
+            "block_size" => Some(Self::BlockSize),
+            _ => None,
+        }
+    }
+}
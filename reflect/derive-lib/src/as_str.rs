@@ -4,18 +4,24 @@ use ::proc_macro2::TokenStream;
 use ::quote::quote;
 use ::syn::{Fields, parse_quote};
 
-use crate::get::{self, match_parsed_attr};
+use crate::get::{self, attrl, match_parsed_attr};
 
 /// Implement `AsStr` for an enum.
 pub fn as_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
     let mut impl_display = false;
     let mut impl_as_ref = false;
+    let mut rename_all = None;
+    let mut impl_serde = false;
+    let mut impl_value_enum = false;
 
     let crate_path = get::crate_path_and(&item.attrs, &["as_str"], |meta| {
         Ok(match_parsed_attr! {
             meta;
             "display" => impl_display = true,
             "as_ref" => impl_as_ref = true,
+            serde => impl_serde = true,
+            value_enum => impl_value_enum = true,
+            rename_all => rename_all = Some(get::list_or_name_value(meta.input, get::case_from_expr)?),
         })
     })?;
 
@@ -32,7 +38,7 @@ pub fn as_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
             }
         })
         .collect::<Vec<::syn::Pat>>();
-    let str_rep = get::variants_as_str_reprs(&item)?;
+    let str_rep = get::variants_as_str_reprs(&item, rename_all)?;
 
     let ident = &item.ident;
 
@@ -58,6 +64,63 @@ pub fn as_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
         }
     });
 
+    let serialize = impl_serde.then(|| {
+        quote! {
+            #[automatically_derived]
+            impl #crate_path::__serde::Serialize for #ident {
+                fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: #crate_path::__serde::Serializer,
+                {
+                    serializer.serialize_str(<Self as #crate_path::AsStr>::as_str(self))
+                }
+            }
+        }
+    });
+
+    let value_enum = if impl_value_enum {
+        let possible_values = item
+            .variants
+            .iter()
+            .zip(&str_rep)
+            .map(|(variant, str_rep)| {
+                let mut hide = false;
+                get::attrs(&variant.attrs, attrl![value_enum], |meta| {
+                    Ok(match_parsed_attr! {
+                        meta;
+                        hide => :hide,
+                    })
+                })?;
+                let help = get::doc_string(&variant.attrs).map(|help| quote!(.help(#help)));
+                let aliases = get::variant_aliases(variant)?;
+
+                Ok(quote! {
+                    #crate_path::__clap::builder::PossibleValue::new(#str_rep)
+                        #help
+                        #(.alias(#aliases))*
+                        .hide(#hide)
+                })
+            })
+            .collect::<::syn::Result<Vec<_>>>()?;
+
+        Some(quote! {
+            #[automatically_derived]
+            impl #crate_path::__clap::ValueEnum for #ident {
+                fn value_variants<'__a>() -> &'__a [Self] {
+                    <Self as #crate_path::Variants>::VARIANTS
+                }
+
+                fn to_possible_value(&self) -> ::core::option::Option<#crate_path::__clap::builder::PossibleValue> {
+                    ::core::option::Option::Some(match self {#(
+                        Self::#variant_pat => #possible_values,
+                    )*})
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     Ok(quote! {
         const _: () = {
 
@@ -72,6 +135,8 @@ pub fn as_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
 
         #as_ref
         #display
+        #serialize
+        #value_enum
 
         };
     })
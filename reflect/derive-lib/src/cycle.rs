@@ -1,22 +1,151 @@
-//! Implementation for `Cycle` derive macro.
+//! Implementation for `Cycle` and `PartialVariants` derive macros.
 
 use ::proc_macro2::TokenStream;
 use ::quote::quote;
+use ::syn::Ident;
 
-use crate::get;
+use crate::get::{self, attrl, match_parsed_attr};
+
+/// Order of variants when cycling.
+struct CycleOrder<'i> {
+    /// Crate path to use.
+    crate_path: ::syn::ExprPath,
+    /// All variants in declaration order.
+    variants: Vec<&'i Ident>,
+    /// Cycled variants in cycle order.
+    order: Vec<&'i Ident>,
+    /// If some variants are skipped or reordered.
+    partial: bool,
+    /// Index in order of next variant, for every variant in declaration order.
+    next: Vec<usize>,
+    /// Index in order of previous variant, for every variant in declaration order.
+    prev: Vec<usize>,
+}
+
+/// Get cycle order of an enum using `order`, `skip` and `saturating` attributes.
+///
+/// # Errors
+/// If the enum contains non-unit variants, or if attributes are malformed.
+fn cycle_order(item: &::syn::ItemEnum) -> ::syn::Result<CycleOrder<'_>> {
+    let mut order = None;
+    let mut saturating = false;
+    let crate_path = get::crate_path_and(&item.attrs, attrl![cycle], |meta| {
+        Ok(match_parsed_attr! {
+            meta;
+            order => order = Some(get::list_or_name_value(meta.input, |expr| match expr {
+                ::syn::Expr::Array(array) => array
+                    .elems
+                    .into_iter()
+                    .map(get::ident_from_expr("order"))
+                    .collect::<::syn::Result<Vec<_>>>(),
+                other => Err(::syn::Error::new_spanned(other, "order expects an array of variants")),
+            })?),
+            "saturating" => :saturating,
+        })
+    })?;
+    let variants = get::unit_variants(item)?;
+
+    let mut skipped = Vec::new();
+    for variant in &item.variants {
+        let mut skip = false;
+        get::attrs(&variant.attrs, attrl![cycle], |meta| {
+            Ok(match_parsed_attr! {
+                meta;
+                "skip" => :skip,
+            })
+        })?;
+        if skip {
+            skipped.push(&variant.ident);
+        }
+    }
+
+    let partial = order.is_some() || !skipped.is_empty();
+    let order = match order {
+        Some(order) => {
+            let mut resolved = Vec::<&Ident>::with_capacity(order.len());
+            for ident in &order {
+                let Some(variant) = variants.iter().find(|variant| **variant == ident) else {
+                    return Err(::syn::Error::new_spanned(ident, "unknown variant in order"));
+                };
+                if resolved.contains(variant) {
+                    return Err(::syn::Error::new_spanned(
+                        ident,
+                        "variant repeated in order",
+                    ));
+                }
+                if skipped.contains(variant) {
+                    return Err(::syn::Error::new_spanned(
+                        ident,
+                        "skipped variant should not be part of order",
+                    ));
+                }
+                resolved.push(variant);
+            }
+            resolved
+        }
+        None => variants
+            .iter()
+            .copied()
+            .filter(|variant| !skipped.contains(variant))
+            .collect(),
+    };
+
+    if order.is_empty() && !variants.is_empty() {
+        return Err(::syn::Error::new_spanned(
+            &item.ident,
+            "at least one variant should be cycled",
+        ));
+    }
+
+    let last = order.len().saturating_sub(1);
+    let (next, prev) = variants
+        .iter()
+        .map(
+            |variant| match order.iter().position(|ordered| ordered == variant) {
+                Some(idx) if saturating => ((idx + 1).min(last), idx.saturating_sub(1)),
+                Some(idx) => ((idx + 1) % order.len(), idx.checked_sub(1).unwrap_or(last)),
+                None => (0, last),
+            },
+        )
+        .unzip();
+
+    Ok(CycleOrder {
+        crate_path,
+        variants,
+        order,
+        partial,
+        next,
+        prev,
+    })
+}
 
 /// Implement `Cycle` for an enum.
 ///
 /// # Errors
-/// If the enum contains non-unit variants.
+/// If the enum contains non-unit variants, or if attributes are malformed.
 pub fn cycle(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
-    let crate_path = get::crate_path(&item.attrs, &["cycle"])?;
-    let variants = get::unit_variants(&item)?;
+    let CycleOrder {
+        crate_path,
+        variants,
+        order,
+        partial,
+        next,
+        prev,
+    } = cycle_order(&item)?;
 
-    let cycle_next = variants.iter().cycle().skip(1);
-    let cycle_prev = variants.iter().cycle().skip(variants.len() - 1);
+    let cycle_next = next.iter().map(|idx| order[*idx]);
+    let cycle_prev = prev.iter().map(|idx| order[*idx]);
     let ident = &item.ident;
 
+    let cycled_variants = partial.then(|| {
+        quote! {
+            #[inline]
+            fn cycled_variants() -> ::core::option::Option<&'static [Self]> {
+                ::core::option::Option::Some(&[#(Self::#order),*])
+            }
+        }
+    });
+
     Ok(quote! {
         const _: () = {
 
@@ -33,6 +162,63 @@ pub fn cycle(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
                     Self::#variants => Self::#cycle_prev,
                 )*}
             }
+
+            #cycled_variants
+        }
+
+        };
+    })
+}
+
+/// Implement `PartialVariants` for an enum.
+///
+/// # Errors
+/// If the enum contains non-unit variants, or if attributes are malformed.
+pub fn partial_variants(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
+    let CycleOrder {
+        crate_path,
+        variants,
+        order,
+        next,
+        prev,
+        ..
+    } = cycle_order(&item)?;
+
+    let ident = &item.ident;
+
+    Ok(quote! {
+        const _: () = {
+
+        /// Cycled variants in cycle order.
+        const ORDER: &[#ident] = &[#(#ident::#order),*];
+
+        #[automatically_derived]
+        impl #crate_path::PartialVariants for #ident {
+            #[inline]
+            fn partial_variants<'a>() -> impl ::core::iter::IntoIterator<Item = &'a Self>
+            where
+                Self: 'a,
+            {
+                ORDER
+            }
+
+            fn partial_cycle_next<'a>(&self) -> &'a Self
+            where
+                Self: 'a + ::core::cmp::PartialEq,
+            {
+                match self {#(
+                    Self::#variants => &ORDER[#next],
+                )*}
+            }
+
+            fn partial_cycle_prev<'a>(&self) -> &'a Self
+            where
+                Self: 'a + ::core::cmp::PartialEq,
+            {
+                match self {#(
+                    Self::#variants => &ORDER[#prev],
+                )*}
+            }
         }
 
         };
@@ -0,0 +1,140 @@
+//! Implementation for `Describe` derive macro.
+
+use ::convert_case::{Case, Casing};
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::syn::{Fields, parse_quote};
+
+use crate::get::{self, attrl, match_parsed_attr};
+
+/// Get a property value from an expression.
+fn prop_value(crate_path: &::syn::ExprPath, expr: ::syn::Expr) -> ::syn::Result<TokenStream> {
+    /// Get a numeric literal, negated if `neg` is true.
+    fn number(
+        crate_path: &::syn::ExprPath,
+        lit: &::syn::Lit,
+        neg: bool,
+    ) -> Option<::syn::Result<TokenStream>> {
+        let sign = neg.then(|| quote!(-));
+        Some(match lit {
+            ::syn::Lit::Int(lit_int) => lit_int
+                .base10_parse::<i64>()
+                .map(|_| quote!(#crate_path::PropValue::Int(#sign #lit_int))),
+            ::syn::Lit::Float(lit_float) => lit_float
+                .base10_parse::<f64>()
+                .map(|_| quote!(#crate_path::PropValue::Float(#sign #lit_float))),
+            _ => return None,
+        })
+    }
+
+    match &expr {
+        ::syn::Expr::Lit(::syn::ExprLit { lit, .. }) => match lit {
+            ::syn::Lit::Str(lit_str) => Some(Ok(quote!(#crate_path::PropValue::Str(#lit_str)))),
+            ::syn::Lit::Bool(lit_bool) => Some(Ok(quote!(#crate_path::PropValue::Bool(#lit_bool)))),
+            lit => number(crate_path, lit, false),
+        },
+        ::syn::Expr::Unary(::syn::ExprUnary {
+            op: ::syn::UnOp::Neg(..),
+            expr: inner,
+            ..
+        }) => match &**inner {
+            ::syn::Expr::Lit(::syn::ExprLit { lit, .. }) => number(crate_path, lit, true),
+            _ => None,
+        },
+        _ => None,
+    }
+    .unwrap_or_else(|| {
+        Err(::syn::Error::new_spanned(
+            expr,
+            "prop values should be string, integer, float or boolean literals",
+        ))
+    })
+}
+
+/// Implement `Describe` for an enum.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn describe(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
+    let crate_path = get::crate_path(&item.attrs, attrl![describe])?;
+
+    let descriptions = item
+        .variants
+        .iter()
+        .map(|variant| {
+            let mut label = None;
+            let mut props = Vec::new();
+            get::attrs(&variant.attrs, attrl![describe], |meta| {
+                Ok(match_parsed_attr! {
+                    meta;
+                    label => label = Some(get::list_or_name_value(meta.input, get::lit_str_from_expr("label"))?),
+                    prop => meta.parse_nested_meta(|prop| {
+                        let key = prop
+                            .path
+                            .get_ident()
+                            .ok_or_else(|| prop.error("prop keys should be idents"))?
+                            .to_string();
+                        let value = prop_value(&crate_path, prop.value()?.parse()?)?;
+                        props.push(quote!((#key, #value)));
+                        Ok(())
+                    })?,
+                })
+            })?;
+
+            let label = label.map_or_else(
+                || variant.ident.to_string().to_case(Case::Title),
+                |label| label.value(),
+            );
+            let description = get::doc_paragraphs(&variant.attrs).join("\n");
+            let description = if description.is_empty() {
+                quote!(::core::option::Option::None)
+            } else {
+                quote!(::core::option::Option::Some(#description))
+            };
+
+            Ok(quote! {
+                #crate_path::Description {
+                    label: #label,
+                    description: #description,
+                    props: &[#(#props),*],
+                }
+            })
+        })
+        .collect::<::syn::Result<Vec<_>>>()?;
+
+    let variant_pat = item
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            match variant.fields {
+                Fields::Named(..) => parse_quote!(#ident{..}),
+                Fields::Unnamed(..) => parse_quote!(#ident(..)),
+                Fields::Unit => parse_quote!(#ident),
+            }
+        })
+        .collect::<Vec<::syn::Pat>>();
+    let indices = 0..variant_pat.len();
+
+    let ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Describe for #ident #ty_generics
+        #where_clause
+        {
+            const DESCRIPTIONS: &'static [#crate_path::Description] = &[#(#descriptions),*];
+
+            fn describe(&self) -> &'static #crate_path::Description {
+                &<Self as #crate_path::Describe>::DESCRIPTIONS[match self {#(
+                    Self::#variant_pat => #indices,
+                )*}]
+            }
+        }
+
+        };
+    })
+}
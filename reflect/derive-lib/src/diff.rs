@@ -0,0 +1,109 @@
+//! Implementation for `Diff` derive macro.
+
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::syn::parse_quote;
+
+use crate::{
+    get::{self, attrl, match_parsed_attr},
+    intermediate::MemberRef,
+};
+
+/// Implement `Diff` for a struct.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn diff(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
+    let mut all_skip = false;
+    let crate_path = get::crate_path_and(&item.attrs, attrl![fields diff], |meta| {
+        let result = match_parsed_attr! {
+            meta;
+            skip => :all_skip,
+        };
+        get::skip_fields_attr(meta, result)
+    })?;
+
+    let mut members = Vec::new();
+    let mut variant_names = Vec::new();
+    let mut field_types = Vec::new();
+    let mut flattened = Vec::new();
+    for (i, field) in item.fields.iter().enumerate() {
+        let mut skip = all_skip;
+        let mut flatten = false;
+        get::attrs(&field.attrs, attrl![fields diff], |meta| {
+            let result = match_parsed_attr! {
+                meta;
+                skip => :skip,
+                flatten => :flatten,
+            };
+            get::skip_fields_attr(meta, result)
+        })?;
+
+        if skip {
+            continue;
+        }
+
+        members.push(MemberRef::from_ident_or(field.ident.as_ref(), i));
+        variant_names.push(get::field_variant_name(field, i)?);
+        field_types.push(get::unwrapped_ty(&field.ty));
+        flattened.push(flatten);
+    }
+
+    let ident = &item.ident;
+    let generics = get::with_predicates(
+        &item.generics,
+        field_types.iter().zip(&flattened).map(|(ty, flatten)| {
+            if *flatten {
+                parse_quote!(#ty: #crate_path::Diff)
+            } else {
+                parse_quote!(#ty: ::core::cmp::PartialEq + ::core::clone::Clone)
+            }
+        }),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Changes of flattened fields are nested in the variant of the field.
+    let field_diffs = members.iter().zip(&variant_names).zip(&flattened).map(|((member, variant), flatten)| {
+        if *flatten {
+            quote! {
+                let (olds, news) = #crate_path::Diff::diff(&old.#member, &new.#member).into_parts();
+                for (old, new) in ::core::iter::zip(olds, news) {
+                    patch.push(
+                        <Self as #crate_path::FieldDelta>::FieldDelta::#variant(old),
+                        <Self as #crate_path::FieldDelta>::FieldDelta::#variant(new),
+                    );
+                }
+            }
+        } else {
+            quote! {
+                if old.#member != new.#member {
+                    patch.push(
+                        <Self as #crate_path::FieldDelta>::FieldDelta::#variant(
+                            ::core::clone::Clone::clone(&old.#member),
+                        ),
+                        <Self as #crate_path::FieldDelta>::FieldDelta::#variant(
+                            ::core::clone::Clone::clone(&new.#member),
+                        ),
+                    );
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Diff for #ident #ty_generics
+        #where_clause
+        {
+            fn diff(old: &Self, new: &Self) -> #crate_path::Patch<Self> {
+                let mut patch = #crate_path::Patch::new();
+                #({ #field_diffs })*
+                patch
+            }
+        }
+
+        };
+    })
+}
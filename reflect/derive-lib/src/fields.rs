@@ -2,10 +2,10 @@
 
 use ::std::borrow::Cow;
 
-use ::convert_case::{Case, Casing, ccase};
-use ::proc_macro2::Span;
+use ::convert_case::{Case, Casing};
+use ::proc_macro2::{Span, TokenStream};
 use ::quote::{ToTokens, format_ident, quote};
-use ::syn::Ident;
+use ::syn::{Ident, parse_quote};
 
 use crate::{
     ext::{BoolExt, ResultExt},
@@ -21,12 +21,14 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
     let mut fields_ref_name = None;
     let mut fields_mut_name = None;
     let mut fields_idx_name = None;
+    let mut impl_serde = false;
 
     let crate_path = get::crate_path_and(&item.attrs, attrl![fields], |meta| {
         Ok(match_parsed_attr! {
             meta;
             skip => :all_skip,
             option => :all_option,
+            serde => :impl_serde,
             fields_name => into_fields_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("fields_name"))?),
             fields_name_ref => fields_ref_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("fields_name_ref"))?),
             fields_name_mut => fields_mut_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("fields_name_mut"))?),
@@ -58,12 +60,12 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
     let fields_idx_name =
         fields_idx_name.unwrap_or_else(|| format_ident!("__{ident}FieldIdx{variance}"));
 
-    let mut field_count = 0usize;
     let mut field_names = Vec::new();
     let mut variant_names = Vec::new();
     let mut field_types = Vec::new();
     let mut variant_docs = Vec::new();
     let mut field_members = Vec::new();
+    let mut flattened = Vec::new();
 
     let lt_name = ident.to_string().to_case(Case::Snake);
     let lt = ::syn::Lifetime::new(&format!("'__{lt_name}_{variance}"), Span::call_site());
@@ -74,12 +76,14 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
         .try_for_each::<_, ::syn::Result<_>>(|(i, field)| {
             let mut skip = all_skip;
             let mut option = all_option;
+            let mut flatten = false;
 
             get::attrs(&field.attrs, attrl![fields], |meta| {
                 Ok(match_parsed_attr! {
                     meta;
                     skip => :skip,
                     option => :option,
+                    flatten => :flatten,
                 })
             })?;
 
@@ -87,22 +91,7 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
                 return Ok(());
             }
 
-            let variant_name = field.ident.as_ref().map_or_else(
-                || Ok(format_ident!("_{i}")),
-                |ident| -> ::syn::Result<_> {
-                    let name = ident.to_string();
-                    let mut name = if let Some(ident) = name.strip_prefix("r#") {
-                        let name = ccase!(pascal, ident);
-                        ::syn::parse_str::<Ident>(&format!("r#{name}"))?
-                    } else {
-                        let name = ccase!(pascal, name);
-                        syn::parse_str::<Ident>(&name)?
-                    };
-                    name.set_span(ident.span());
-
-                    Ok(name)
-                },
-            )?;
+            let variant_name = get::field_variant_name(field, i)?;
 
             let member = MemberRef::from_ident_or(field.ident.as_ref(), i);
 
@@ -115,22 +104,123 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
 
             let doc = format!("Variant for the {member} field");
 
-            field_count += 1;
+            // The index enum cannot be generic, as such neither can flattened fields.
+            if flatten && !get::used_generics(&item.generics, ty).params.is_empty() {
+                return Err(::syn::Error::new_spanned(
+                    ty,
+                    "flattened fields may not use generic parameters",
+                ));
+            }
 
             field_names.push(field_name);
             variant_names.push(variant_name);
             field_types.push(ty);
             variant_docs.push(doc);
             field_members.push(member);
+            flattened.push(flatten.then_some(ty));
 
             Ok(())
         })?;
 
-    let expansion = if matches!(item.fields, ::syn::Fields::Unnamed(..)) {
-        quote! { (#(#field_names),*) }
-    } else {
-        quote! { {#(#field_names),*} }
-    };
+    // Braced patterns are used for tuple structs as well so skipped fields may be ignored.
+    let expansion = field_members
+        .iter()
+        .zip(&field_names)
+        .map(|(member, name)| match member {
+            MemberRef::Ident(..) => quote!(#name),
+            MemberRef::Idx(..) => quote!(#member: #name),
+        });
+    let expansion = quote! { { #(#expansion,)* .. } };
+
+    let has_flatten = flattened.iter().any(Option::is_some);
+    let field_count = field_names.len();
+    // Field names are used as strings, matching those of serde derives.
+    let names = field_members
+        .iter()
+        .map(|member| {
+            let name = member.to_string();
+            name.strip_prefix("r#")
+                .map_or_else(|| name.clone(), str::to_owned)
+        })
+        .collect::<Vec<_>>();
+    // Variant names as given by the `AsStr` derive.
+    let variant_strs = variant_names
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let fields_assoc =
+        |ty: &::syn::Type, assoc: TokenStream| quote!(<#ty as #crate_path::Fields>::#assoc);
+    let into_payloads = field_types
+        .iter()
+        .zip(&flattened)
+        .map(|(ty, flat)| match flat {
+            Some(..) => fields_assoc(ty, quote!(Field)),
+            None => ty.to_token_stream(),
+        })
+        .collect::<Vec<_>>();
+    let ref_payloads = field_types
+        .iter()
+        .zip(&flattened)
+        .map(|(ty, flat)| match flat {
+            Some(..) => fields_assoc(ty, quote!(FieldRef<#lt>)),
+            None => quote!(&#lt #ty),
+        })
+        .collect::<Vec<_>>();
+    let mut_payloads = field_types
+        .iter()
+        .zip(&flattened)
+        .map(|(ty, flat)| match flat {
+            Some(..) => fields_assoc(ty, quote!(FieldMut<#lt>)),
+            None => quote!(&#lt mut #ty),
+        })
+        .collect::<Vec<_>>();
+
+    // Flattened fields take up as many indices as their nested struct has fields.
+    let sizes = flattened
+        .iter()
+        .map(|flat| match flat {
+            Some(ty) => {
+                let idx = fields_assoc(ty, quote!(FieldIdx));
+                quote!(<#idx as #crate_path::Variants>::VARIANTS.len())
+            }
+            None => quote!(1usize),
+        })
+        .collect::<Vec<_>>();
+    let offsets = (0..sizes.len())
+        .map(|i| {
+            let before = &sizes[..i];
+            quote!(0usize #(+ #before)*)
+        })
+        .collect::<Vec<_>>();
+
+    let as_ref_arms = variant_names
+        .iter()
+        .zip(&flattened)
+        .zip(&offsets)
+        .map(|((variant, flat), offset)| match flat {
+            Some(ty) => {
+                let idx = fields_assoc(ty, quote!(FieldIdx));
+                quote! {
+                    Self::#variant(field) => &<#fields_idx_name as #crate_path::Variants>::VARIANTS[
+                        #offset + #crate_path::Variants::index_of(
+                            ::core::convert::AsRef::<#idx>::as_ref(field),
+                        )
+                    ],
+                }
+            }
+            None => quote!(Self::#variant(..) => &#fields_idx_name::#variant,),
+        })
+        .collect::<Vec<_>>();
+
+    // Field enums only use the generics referenced by field types, as unused params are errors.
+    let field_generics = get::used_generics(&item.generics, quote!(#(#field_types)*));
+    let field_lt_generics = get::with_lifetime(&field_generics, &lt);
+    let struct_lt_generics = get::with_lifetime(&item.generics, &lt);
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let (_, field_ty_generics, field_where_clause) = field_generics.split_for_impl();
+    let (_, field_lt_ty_generics, field_lt_where_clause) = field_lt_generics.split_for_impl();
+    let (struct_lt_impl_generics, _, _) = struct_lt_generics.split_for_impl();
 
     let vis = &item.vis;
     let doc = format!(
@@ -143,23 +233,12 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
             quote! {
                 #[doc = #doc]
                 #[automatically_derived]
-                #vis enum #into_fields_name {
+                #vis enum #into_fields_name #field_generics #field_where_clause {
                     #(
                     #[doc = #variant_docs]
-                    #variant_names(#field_types),
+                    #variant_names(#into_payloads),
                     )*
                 }
-
-                #[automatically_derived]
-                impl ::core::convert::AsRef<#fields_idx_name> for #into_fields_name {
-                    fn as_ref(&self) -> &#fields_idx_name {
-                        match self {
-                            #(
-                            Self:: #variant_names(..) => &#fields_idx_name::#variant_names,
-                            )*
-                        }
-                    }
-                }
             }
         })
         .split_result();
@@ -174,23 +253,12 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
             quote! {
                 #[doc = #doc]
                 #[automatically_derived]
-                #vis enum #fields_ref_name<#lt> {
+                #vis enum #fields_ref_name #field_lt_generics #field_lt_where_clause {
                     #(
                     #[doc = #variant_docs]
-                    #variant_names(&#lt #field_types),
+                    #variant_names(#ref_payloads),
                     )*
                 }
-
-                #[automatically_derived]
-                impl ::core::convert::AsRef<#fields_idx_name> for #fields_ref_name<'_> {
-                    fn as_ref(&self) -> &#fields_idx_name {
-                        match self {
-                            #(
-                            Self:: #variant_names(..) => &#fields_idx_name::#variant_names,
-                            )*
-                        }
-                    }
-                }
             }
         })
         .split_result();
@@ -205,26 +273,161 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
             quote! {
                 #[doc = #doc]
                 #[automatically_derived]
-                #vis enum #fields_mut_name<#lt> {
+                #vis enum #fields_mut_name #field_lt_generics #field_lt_where_clause {
                     #(
                     #[doc = #variant_docs]
-                    #variant_names(&#lt mut #field_types),
+                    #variant_names(#mut_payloads),
                     )*
                 }
+            }
+        })
+        .split_result();
 
+    // Implemented in a separate scope as the index enum may be hidden.
+    let as_ref_impls = [
+        (&into_fields_name, &field_generics),
+        (&fields_ref_name, &field_lt_generics),
+        (&fields_mut_name, &field_lt_generics),
+    ]
+    .into_iter()
+    .map(|(name, generics)| {
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::AsRef<#fields_idx_name> for #name #ty_generics
+            #where_clause
+            {
+                fn as_ref(&self) -> &#fields_idx_name {
+                    match self {
+                        #(#as_ref_arms)*
+                    }
+                }
+            }
+        }
+    })
+    .collect::<TokenStream>();
+
+    let serde_impls = impl_serde.then(|| {
+        let serialize = |name: &Ident, generics: &::syn::Generics, payloads: &[TokenStream]| {
+            let generics = get::with_predicates(
+                generics,
+                payloads
+                    .iter()
+                    .map(|ty| parse_quote!(#ty: #crate_path::__serde::Serialize)),
+            );
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            quote! {
                 #[automatically_derived]
-                impl ::core::convert::AsRef<#fields_idx_name> for #fields_mut_name<'_> {
-                    fn as_ref(&self) -> &#fields_idx_name {
-                        match self {
-                            #(
-                            Self:: #variant_names(..) => &#fields_idx_name::#variant_names,
-                            )*
+                impl #impl_generics #crate_path::__serde::Serialize for #name #ty_generics
+                #where_clause
+                {
+                    fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                    where
+                        __S: #crate_path::__serde::Serializer,
+                    {
+                        use #crate_path::__serde::ser::SerializeMap;
+                        let mut map = serializer.serialize_map(::core::option::Option::Some(1))?;
+                        match self {#(
+                            Self::#variant_names(value) => map.serialize_entry(#names, value)?,
+                        )*}
+                        map.end()
+                    }
+                }
+            }
+        };
+        let ref_bounds = field_types
+            .iter()
+            .zip(&flattened)
+            .zip(&ref_payloads)
+            .map(|((ty, flat), payload)| match flat {
+                Some(..) => payload.clone(),
+                None => ty.to_token_stream(),
+            })
+            .collect::<Vec<_>>();
+        let serialize_into_fields = serialize(&into_fields_name, &field_generics, &into_payloads);
+        let serialize_fields_ref = serialize(&fields_ref_name, &field_lt_generics, &ref_bounds);
+        let expecting = format!("a map with a single field of {ident}");
+
+        let de_generics = get::with_predicates(
+            &get::with_lifetime(&field_generics, &parse_quote!('__de)),
+            into_payloads
+                .iter()
+                .map(|ty| parse_quote!(#ty: #crate_path::__serde::Deserialize<'__de>)),
+        );
+        let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
+        quote! {
+            #serialize_into_fields
+            #serialize_fields_ref
+
+            #[automatically_derived]
+            impl #crate_path::__serde::Serialize for #fields_idx_name {
+                fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: #crate_path::__serde::Serializer,
+                {
+                    serializer.serialize_str(#crate_path::FieldName::field_name(self))
+                }
+            }
+
+            #[automatically_derived]
+            impl<'__de> #crate_path::__serde::Deserialize<'__de> for #fields_idx_name {
+                fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: #crate_path::__serde::Deserializer<'__de>,
+                {
+                    #crate_path::deserialize_field_name(deserializer)
+                }
+            }
+
+            #[automatically_derived]
+            impl #de_impl_generics #crate_path::__serde::Deserialize<'__de> for #into_fields_name #field_ty_generics
+            #de_where_clause
+            {
+                fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: #crate_path::__serde::Deserializer<'__de>,
+                {
+                    use #crate_path::__serde::de::{Error, IgnoredAny, MapAccess, Visitor};
+
+                    struct FieldVisitor #field_generics (
+                        ::core::marker::PhantomData<fn() -> #into_fields_name #field_ty_generics>,
+                    ) #field_where_clause;
+
+                    impl #de_impl_generics Visitor<'__de> for FieldVisitor #field_ty_generics
+                    #de_where_clause
+                    {
+                        type Value = #into_fields_name #field_ty_generics;
+
+                        fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                            formatter.write_str(#expecting)
+                        }
+
+                        fn visit_map<__A>(self, mut map: __A) -> ::core::result::Result<Self::Value, __A::Error>
+                        where
+                            __A: MapAccess<'__de>,
+                        {
+                            let ::core::option::Option::Some(key) = map.next_key::<::std::string::String>()? else {
+                                return ::core::result::Result::Err(__A::Error::invalid_length(0, &self));
+                            };
+                            let field = match key.as_str() {
+                                #(
+                                #names => #into_fields_name::#variant_names(map.next_value()?),
+                                )*
+                                key => return ::core::result::Result::Err(__A::Error::unknown_field(key, &[#(#names),*])),
+                            };
+                            if map.next_key::<IgnoredAny>()?.is_some() {
+                                return ::core::result::Result::Err(__A::Error::invalid_length(2, &self));
+                            }
+                            ::core::result::Result::Ok(field)
                         }
                     }
+
+                    deserializer.deserialize_map(FieldVisitor(::core::marker::PhantomData))
                 }
             }
-        })
-        .split_result();
+        }
+    });
 
     let doc = format!(
         "[FieldsIdx::FieldIdx][{}::FieldsIdx::FieldIdx] enum for {ident}",
@@ -233,6 +436,34 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
     let [fields_idx_outer, fields_idx_inner] = is_fields_idx_public
         .to_result()
         .map_either(|_| {
+            if has_flatten {
+                let idx_variants =
+                    variant_names
+                        .iter()
+                        .zip(&flattened)
+                        .map(|(variant, flat)| match flat {
+                            Some(ty) => {
+                                let idx = fields_assoc(ty, quote!(FieldIdx));
+                                quote!(#variant(#idx))
+                            }
+                            None => quote!(#variant),
+                        });
+                return quote! {
+                    #[doc = #doc]
+                    #[automatically_derived]
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+                    #vis enum #fields_idx_name {
+                        #(
+                        #[doc = #variant_docs]
+                        #idx_variants,
+                        )*
+                    }
+                };
+            }
+            // Field names are accepted as well, as they are by flattening index enums.
+            let aliases = names.iter().zip(&variant_strs).map(|(name, variant)| {
+                (name != variant).then(|| quote!(#[from_str(alias = #name)]))
+            });
             quote! {
                 #[doc = #doc]
                 #[automatically_derived]
@@ -245,19 +476,134 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
                 #vis enum #fields_idx_name {
                     #(
                     #[doc = #variant_docs]
+                    #aliases
                     #variant_names,
                     )*
                 }
+            }
+        })
+        .split_result();
+    let idx_impls = if has_flatten {
+        flatten_idx_impls(FlattenIdx {
+            crate_path: &crate_path,
+            fields_idx_name: &fields_idx_name,
+            variant_names: &variant_names,
+            variant_strs: &variant_strs,
+            flattened: &flattened,
+            names: &names,
+            offsets: &offsets,
+        })
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl #crate_path::FieldName for #fields_idx_name {
+                const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+                const VARIANT_NAMES: &'static [&'static str] = &[#(#variant_strs),*];
+
+                fn field_name<'a>(&self) -> &'a str {
+                    match self {#(
+                        Self::#variant_names => #names,
+                    )*}
+                }
 
-                #[automatically_derived]
-                impl ::core::convert::AsRef<#fields_idx_name> for #fields_idx_name {
-                    fn as_ref(&self) -> &#fields_idx_name {
-                        self
+                fn from_field_name(s: &str) -> ::core::option::Option<Self> {
+                    match s {
+                        #(#names => ::core::option::Option::Some(Self::#variant_names),)*
+                        _ => ::core::option::Option::None,
                     }
                 }
             }
+        }
+    };
+
+    // Fields with flattened fields are collected into vectors, as their count is not known.
+    let collection = |field: TokenStream| {
+        if has_flatten {
+            quote!(::std::vec::Vec<#field>)
+        } else {
+            quote!([#field; #field_count])
+        }
+    };
+    let collect = |name: &Ident, nested: TokenStream| {
+        if !has_flatten {
+            return quote!([#(#name::#variant_names(#field_names)),*]);
+        }
+        let pushes = variant_names.iter().zip(&field_names).zip(&flattened).map(
+            |((variant, field_name), flat)| match flat {
+                Some(..) => quote! {
+                    __fields.extend(#nested(#field_name).into_iter().map(#name::#variant));
+                },
+                None => quote!(__fields.push(#name::#variant(#field_name));),
+            },
+        );
+        quote! {
+            let mut __fields = ::std::vec::Vec::with_capacity(0usize #(+ #sizes)*);
+            #(#pushes)*
+            __fields
+        }
+    };
+    let into_fields_ty = collection(quote!(Self::Field));
+    let fields_ref_ty = collection(quote!(#fields_ref_name #field_lt_ty_generics));
+    let fields_mut_ty = collection(quote!(#fields_mut_name #field_lt_ty_generics));
+    let into_fields_body = collect(
+        &into_fields_name,
+        quote!(#crate_path::IntoFields::into_fields),
+    );
+    let fields_ref_body = collect(&fields_ref_name, quote!(#crate_path::Fields::fields));
+    let fields_mut_body = collect(&fields_mut_name, quote!(#crate_path::Fields::fields_mut));
+
+    let get_arms = |name: &Ident, nested: TokenStream| {
+        variant_names
+            .iter()
+            .zip(&field_names)
+            .zip(&flattened)
+            .map(|((variant, field_name), flat)| match flat {
+                Some(..) => quote! {
+                    #fields_idx_name::#variant(__idx) => #name::#variant(#nested(#field_name, __idx)),
+                },
+                None => quote!(#fields_idx_name::#variant => #name::#variant(#field_name),),
+            })
+            .collect::<TokenStream>()
+    };
+    let get_ref_arms = get_arms(&fields_ref_name, quote!(#crate_path::FieldsIdx::get));
+    let get_mut_arms = get_arms(&fields_mut_name, quote!(#crate_path::FieldsIdxMut::get_mut));
+
+    let (delta_arms, replace_delta_arms) = variant_names
+        .iter()
+        .zip(&field_members)
+        .zip(&flattened)
+        .map(|((variant, member), flat)| match flat {
+            Some(..) => (
+                quote! {
+                    #into_fields_name::#variant(value) => #crate_path::FieldDelta::delta(&mut self.#member, value),
+                },
+                quote! {
+                    #into_fields_name::#variant(value) => #into_fields_name::#variant(
+                        #crate_path::ReplaceDelta::replace_delta(&mut self.#member, value),
+                    ),
+                },
+            ),
+            None => (
+                quote!(#into_fields_name::#variant(value) => self.#member = value,),
+                quote! {
+                    #into_fields_name::#variant(value) => #into_fields_name::#variant(
+                        ::core::mem::replace(&mut self.#member, value),
+                    ),
+                },
+            ),
         })
-        .split_result();
+        .unzip::<_, _, TokenStream, TokenStream>();
+
+    // Fields requires implementors to outlive any lifetime.
+    let fields_generics = if item.generics.params.is_empty() {
+        Cow::Borrowed(&item.generics)
+    } else {
+        Cow::Owned(get::with_predicates(
+            &item.generics,
+            [parse_quote!(Self: 'static)],
+        ))
+    };
+    let fields_where_clause = &fields_generics.where_clause;
 
     Ok(quote! {
         #fields_ref_outer
@@ -269,100 +615,375 @@ pub fn fields(item: ::syn::ItemStruct) -> ::syn::Result<::proc_macro2::TokenStre
             #fields_mut_inner
             #into_fields_inner
             #fields_idx_inner
+            #as_ref_impls
+            #idx_impls
+            #serde_impls
+
+            #[automatically_derived]
+            impl ::core::convert::AsRef<#fields_idx_name> for #fields_idx_name {
+                fn as_ref(&self) -> &#fields_idx_name {
+                    self
+                }
+            }
 
             #[automatically_derived]
-            impl #crate_path::IntoFields for #ident {
-                type Field = #into_fields_name;
-                type IntoFields = [Self::Field; #field_count];
+            impl #impl_generics #crate_path::IntoFields for #ident #ty_generics
+            #where_clause
+            {
+                type Field = #into_fields_name #field_ty_generics;
+                type IntoFields = #into_fields_ty;
 
                 fn into_fields(self) -> Self::IntoFields {
                     let Self #expansion = self;
-                    [#(#into_fields_name::#variant_names(#field_names)),*]
+                    #into_fields_body
                 }
             }
 
             #[automatically_derived]
-            impl<#lt> #crate_path::IntoFields for &#lt #ident {
-                type Field = #fields_ref_name<#lt>;
-                type IntoFields = [Self::Field; #field_count];
+            impl #struct_lt_impl_generics #crate_path::IntoFields for &#lt #ident #ty_generics
+            #where_clause
+            {
+                type Field = #fields_ref_name #field_lt_ty_generics;
+                type IntoFields = #into_fields_ty;
 
                 fn into_fields(self) -> Self::IntoFields {
                     let #ident #expansion = self;
-                    [#(#fields_ref_name::#variant_names(#field_names)),*]
+                    #fields_ref_body
                 }
             }
 
             #[automatically_derived]
-            impl<#lt> #crate_path::IntoFields for &#lt mut #ident {
-                type Field = #fields_mut_name<#lt>;
-                type IntoFields = [Self::Field; #field_count];
+            impl #struct_lt_impl_generics #crate_path::IntoFields for &#lt mut #ident #ty_generics
+            #where_clause
+            {
+                type Field = #fields_mut_name #field_lt_ty_generics;
+                type IntoFields = #into_fields_ty;
 
                 fn into_fields(self) -> Self::IntoFields {
                     let #ident #expansion = self;
-                    [#(#fields_mut_name::#variant_names(#field_names)),*]
+                    #fields_mut_body
                 }
             }
 
             #[automatically_derived]
-            impl #crate_path::FieldsIdx for #ident {
+            impl #impl_generics #crate_path::FieldsIdx for #ident #ty_generics
+            #where_clause
+            {
                 type FieldIdx = #fields_idx_name;
-                type FieldRef<#lt> = #fields_ref_name<#lt>;
+                type FieldRef<#lt> = #fields_ref_name #field_lt_ty_generics
+                where
+                    Self: #lt;
 
-                fn get(&self, idx: Self::FieldIdx) -> #fields_ref_name<'_> {
+                fn get(&self, idx: Self::FieldIdx) -> Self::FieldRef<'_> {
                     let #ident #expansion = self;
                     match idx {
-                        #(
-                        #fields_idx_name::#variant_names => #fields_ref_name::#variant_names(#field_names),
-                        )*
+                        #get_ref_arms
                     }
                 }
             }
 
             #[automatically_derived]
-            impl #crate_path::FieldsIdxMut for #ident {
-                type FieldMut<#lt> = #fields_mut_name<#lt>;
-
-                fn get_mut(&mut self, idx: Self::FieldIdx) -> #fields_mut_name<'_> {
+            impl #impl_generics #crate_path::FieldsIdxMut for #ident #ty_generics
+            #where_clause
+            {
+                type FieldMut<#lt> = #fields_mut_name #field_lt_ty_generics
+                where
+                    Self: #lt;
+
+                fn get_mut(&mut self, idx: Self::FieldIdx) -> Self::FieldMut<'_> {
                     let #ident #expansion = self;
                     match idx {
-                        #(
-                        #fields_idx_name::#variant_names => #fields_mut_name::#variant_names(#field_names),
-                        )*
+                        #get_mut_arms
                     }
                 }
             }
 
             #[automatically_derived]
-            impl #crate_path::FieldDelta for #ident {
-                type FieldDelta = #into_fields_name;
+            impl #impl_generics #crate_path::FieldDelta for #ident #ty_generics
+            #where_clause
+            {
+                type FieldDelta = #into_fields_name #field_ty_generics;
                 fn delta(&mut self, delta: Self::FieldDelta) {
                     match delta {
-                        #(
-                        #into_fields_name::#variant_names(value) => self.#field_members = value,
-                        )*
+                        #delta_arms
                     }
                 }
             }
 
             #[automatically_derived]
-            impl #crate_path::Fields for #ident {
-                type Field = #into_fields_name;
+            impl #impl_generics #crate_path::ReplaceDelta for #ident #ty_generics
+            #where_clause
+            {
+                fn replace_delta(&mut self, delta: Self::FieldDelta) -> Self::FieldDelta {
+                    match delta {
+                        #replace_delta_arms
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #crate_path::Fields for #ident #ty_generics
+            #fields_where_clause
+            {
+                type Field = #into_fields_name #field_ty_generics;
                 type FieldIdx = #fields_idx_name;
-                type FieldRef<#lt> = #fields_ref_name<#lt>;
-                type FieldMut<#lt> = #fields_mut_name<#lt>;
-                type FieldsRef<#lt> = [#fields_ref_name<#lt>; #field_count];
-                type FieldsMut<#lt> = [#fields_mut_name<#lt>; #field_count];
+                type FieldRef<#lt> = #fields_ref_name #field_lt_ty_generics;
+                type FieldMut<#lt> = #fields_mut_name #field_lt_ty_generics;
+                type FieldsRef<#lt> = #fields_ref_ty
+                where
+                    Self: #lt;
+                type FieldsMut<#lt> = #fields_mut_ty
+                where
+                    Self: #lt;
 
                 #[inline]
-                fn fields(&self) -> <Self as Fields>::FieldsRef<'_> {
-                    self.into_fields()
+                fn fields(&self) -> <Self as #crate_path::Fields>::FieldsRef<'_> {
+                    #crate_path::IntoFields::into_fields(self)
                 }
 
                 #[inline]
-                fn fields_mut(&mut self) -> <Self as Fields>::FieldsMut<'_> {
-                    self.into_fields()
+                fn fields_mut(&mut self) -> <Self as #crate_path::Fields>::FieldsMut<'_> {
+                    #crate_path::IntoFields::into_fields(self)
                 }
             }
         };
     })
 }
+
+/// Input for [flatten_idx_impls].
+struct FlattenIdx<'a> {
+    /// Path to reflect crate.
+    crate_path: &'a ::syn::ExprPath,
+    /// Name of index enum.
+    fields_idx_name: &'a Ident,
+    /// Variant of each field.
+    variant_names: &'a [Ident],
+    /// Variant of each field as a string.
+    variant_strs: &'a [String],
+    /// Type of each flattened field.
+    flattened: &'a [Option<&'a ::syn::Type>],
+    /// String name of each field.
+    names: &'a [String],
+    /// Offset of each field in `VARIANTS`.
+    offsets: &'a [TokenStream],
+}
+
+/// Implement the traits otherwise derived for an index enum with flattened fields,
+/// whose variants are the leaf fields followed by those of the nested structs.
+fn flatten_idx_impls(
+    FlattenIdx {
+        crate_path,
+        fields_idx_name,
+        variant_names,
+        variant_strs,
+        flattened,
+        names,
+        offsets,
+    }: FlattenIdx,
+) -> TokenStream {
+    let nested_idx = |ty: &::syn::Type| quote!(<#ty as #crate_path::Fields>::FieldIdx);
+    let variant_count = offsets.iter().zip(flattened).next_back().map_or_else(
+        || quote!(0usize),
+        |(offset, flat)| match flat {
+            Some(ty) => {
+                let idx = nested_idx(ty);
+                quote!(#offset + <#idx as #crate_path::Variants>::VARIANTS.len())
+            }
+            None => quote!(#offset + 1usize),
+        },
+    );
+
+    // Leaf variants are preferred as fill values, as nested structs may have no fields.
+    let fill = variant_names
+        .iter()
+        .zip(flattened)
+        .find(|(_, flat)| flat.is_none())
+        .or_else(|| variant_names.iter().zip(flattened).next())
+        .map(|(variant, flat)| match flat {
+            Some(ty) => {
+                let idx = nested_idx(ty);
+                quote!(Self::#variant(<#idx as #crate_path::Variants>::VARIANTS[0]))
+            }
+            None => quote!(Self::#variant),
+        });
+
+    let mut fill_variants = TokenStream::new();
+    let mut index_arms = TokenStream::new();
+    let mut field_name_parts = Vec::new();
+    let mut variant_name_parts = Vec::new();
+    let mut leaf_field_arms = TokenStream::new();
+    let mut nested_field_arms = TokenStream::new();
+    let mut leaf_arms = TokenStream::new();
+    let mut nested_arms = TokenStream::new();
+    for ((((variant, variant_str), flat), name), offset) in variant_names
+        .iter()
+        .zip(variant_strs)
+        .zip(flattened)
+        .zip(names)
+        .zip(offsets)
+    {
+        // Field names are accepted as well when parsing, as for index enums without flattening.
+        let patterns = if name == variant_str {
+            quote!(#name)
+        } else {
+            quote!(#variant_str | #name)
+        };
+        match flat {
+            Some(ty) => {
+                let idx = nested_idx(ty);
+                fill_variants.extend(quote! {
+                    let nested = <#idx as #crate_path::Variants>::VARIANTS;
+                    let mut i = 0usize;
+                    while i < nested.len() {
+                        variants[#offset + i] = Self::#variant(nested[i]);
+                        i += 1;
+                    }
+                });
+                index_arms.extend(quote! {
+                    Self::#variant(idx) => #offset + #crate_path::Variants::index_of(idx),
+                });
+                let field_prefix = format!("{name}.");
+                let variant_prefix = format!("{variant_str}.");
+                field_name_parts.push(quote! {
+                    (#field_prefix, <#idx as #crate_path::FieldName>::FIELD_NAMES)
+                });
+                variant_name_parts.push(quote! {
+                    (#variant_prefix, <#idx as #crate_path::FieldName>::VARIANT_NAMES)
+                });
+                nested_field_arms.extend(quote! {
+                    #name => #crate_path::FieldName::from_field_name(nested).map(Self::#variant),
+                });
+                nested_arms.extend(quote! {
+                    #patterns => #crate_path::ParseVariant::parse_variant(nested).map(Self::#variant),
+                });
+            }
+            None => {
+                fill_variants.extend(quote!(variants[#offset] = Self::#variant;));
+                index_arms.extend(quote!(Self::#variant => #offset,));
+                field_name_parts.push(quote!(("", &[#name])));
+                variant_name_parts.push(quote!(("", &[#variant_str])));
+                leaf_field_arms
+                    .extend(quote!(#name => return ::core::option::Option::Some(Self::#variant),));
+                leaf_arms.extend(
+                    quote!(#patterns => return ::core::option::Option::Some(Self::#variant),),
+                );
+            }
+        }
+    }
+    let leaf_match = |arms: &TokenStream| {
+        (!arms.is_empty()).then(|| {
+            quote! {
+                match s {
+                    #arms
+                    _ => {}
+                }
+            }
+        })
+    };
+    let leaf_field_match = leaf_match(&leaf_field_arms);
+    let leaf_match = leaf_match(&leaf_arms);
+
+    // Names of nested fields are joined at compile time, as the index enum is not generic.
+    let joined_names = |parts: &[TokenStream]| {
+        quote! {{
+            const PARTS: &#crate_path::NameParts = &[#(#parts),*];
+            const JOINED: [u8; #crate_path::joined_names_len(PARTS)] =
+                #crate_path::join_names(PARTS);
+            const NAMES: [&str; #crate_path::names_count(PARTS)] =
+                #crate_path::split_names(&JOINED, PARTS);
+            &NAMES
+        }}
+    };
+    let field_names = joined_names(&field_name_parts);
+    let variant_names = joined_names(&variant_name_parts);
+    let expected = joined_names(&[variant_name_parts, field_name_parts].concat());
+    let type_name = fields_idx_name.to_string();
+
+    quote! {
+        #[automatically_derived]
+        unsafe impl #crate_path::Variants for #fields_idx_name {
+            const VARIANTS: &[Self] = &{
+                let mut variants = [#fill; #variant_count];
+                #fill_variants
+                variants
+            };
+
+            fn index_of(&self) -> usize {
+                match self {
+                    #index_arms
+                }
+            }
+
+            fn variant_lifetime_cast<'a>(value: &Self) -> &'a Self {
+                &<Self as #crate_path::Variants>::VARIANTS[#crate_path::Variants::index_of(value)]
+            }
+        }
+
+        #[automatically_derived]
+        unsafe impl #crate_path::Cycle for #fields_idx_name {
+            fn cycle_next(&self) -> Self {
+                let variants = <Self as #crate_path::Variants>::VARIANTS;
+                variants[(#crate_path::Variants::index_of(self) + 1) % variants.len()]
+            }
+
+            fn cycle_prev(&self) -> Self {
+                let variants = <Self as #crate_path::Variants>::VARIANTS;
+                variants[(#crate_path::Variants::index_of(self) + variants.len() - 1) % variants.len()]
+            }
+        }
+
+        #[automatically_derived]
+        impl #crate_path::FieldName for #fields_idx_name {
+            const FIELD_NAMES: &'static [&'static str] = #field_names;
+            const VARIANT_NAMES: &'static [&'static str] = #variant_names;
+
+            #[inline]
+            fn field_name<'a>(&self) -> &'a str {
+                <Self as #crate_path::FieldName>::FIELD_NAMES[#crate_path::Variants::index_of(self)]
+            }
+
+            fn from_field_name(s: &str) -> ::core::option::Option<Self> {
+                #leaf_field_match
+                let (name, nested) = s.split_once('.')?;
+                match name {
+                    #nested_field_arms
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #crate_path::AsStr for #fields_idx_name {
+            #[inline]
+            fn as_str<'a>(&self) -> &'a str {
+                <Self as #crate_path::FieldName>::VARIANT_NAMES[#crate_path::Variants::index_of(self)]
+            }
+        }
+
+        #[automatically_derived]
+        impl #crate_path::ParseVariant for #fields_idx_name {
+            const TYPE_NAME: &'static str = #type_name;
+            const NAMES: &'static [&'static str] = #expected;
+
+            fn parse_variant(s: &str) -> ::core::option::Option<Self> {
+                #leaf_match
+                let (name, nested) = s.split_once('.')?;
+                match name {
+                    #nested_arms
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #crate_path::FromStr for #fields_idx_name {
+            type Err = #crate_path::UnknownVariant;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as #crate_path::ParseVariant>::parse_variant(s)
+                    .ok_or_else(|| #crate_path::UnknownVariant::new::<Self>(s))
+            }
+        }
+
+    }
+}
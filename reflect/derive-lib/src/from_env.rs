@@ -0,0 +1,143 @@
+//! Implementation for `FromEnv` derive macro.
+
+use ::convert_case::{Case, Casing};
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::syn::parse_quote;
+
+use crate::{
+    get::{self, attrl, match_parsed_attr},
+    intermediate::MemberRef,
+};
+
+/// Implement `FromEnv` for a struct.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn from_env(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
+    let mut all_skip = false;
+    let mut all_option = false;
+    let crate_path = get::crate_path_and(&item.attrs, attrl![fields from_env], |meta| {
+        let result = match_parsed_attr! {
+            meta;
+            skip => :all_skip,
+            option => :all_option,
+        };
+        get::skip_fields_attr(meta, result)
+    })?;
+
+    let mut loads = TokenStream::new();
+    let mut bounds = Vec::<::syn::WherePredicate>::new();
+    let (impl_generics, ty_generics, _) = item.generics.split_for_impl();
+    let ident = &item.ident;
+
+    for (i, field) in item.fields.iter().enumerate() {
+        let ty = get::unwrapped_ty(&field.ty);
+        let mut skip = all_skip;
+        // Option fields are unset by empty values unless marked otherwise.
+        let mut option = all_option || get::is_option(ty);
+        let mut flatten = false;
+        let mut rename = None;
+        get::attrs(&field.attrs, attrl![fields from_env], |meta| {
+            let result = match_parsed_attr! {
+                meta;
+                skip => :skip,
+                option => :option,
+                flatten => :flatten,
+                rename => rename = Some(get::list_or_name_value(meta.input, get::lit_str_from_expr("rename"))?),
+            };
+            get::skip_fields_attr(meta, result)
+        })?;
+
+        if skip {
+            continue;
+        }
+
+        let member = MemberRef::from_ident_or(field.ident.as_ref(), i);
+        let variant = get::field_variant_name(field, i)?;
+        let name = rename.map_or_else(
+            || {
+                let name = member.to_string();
+                name.strip_prefix("r#")
+                    .unwrap_or(&name)
+                    .to_case(Case::Constant)
+            },
+            |rename| rename.value(),
+        );
+
+        if flatten {
+            bounds.push(parse_quote!(#ty: #crate_path::FromEnv));
+            let prefix = format!("{name}_");
+            loads.extend(quote! {
+                errors.extend(#crate_path::FromEnv::update_from_env(
+                    &mut self.#member,
+                    &#crate_path::env_var_name(prefix, #prefix),
+                    env,
+                ));
+            });
+            continue;
+        }
+
+        let option_ty = option.then(|| get::option_ty(ty)).flatten();
+        let value_ty = option_ty.unwrap_or(ty);
+        bounds.push(parse_quote!(#value_ty: #crate_path::FromStr));
+        bounds.push(parse_quote! {
+            <#value_ty as #crate_path::FromStr>::Err: ::core::convert::Into<
+                ::std::boxed::Box<dyn ::core::error::Error + ::core::marker::Send + ::core::marker::Sync>,
+            >
+        });
+
+        // Empty values unset option fields.
+        let parse = if option_ty.is_some() {
+            quote! {
+                if value.is_empty() {
+                    ::core::option::Option::Some(::core::option::Option::None)
+                } else {
+                    errors
+                        .parse::<#value_ty>(&var, &value)
+                        .map(::core::option::Option::Some)
+                }
+            }
+        } else {
+            quote!(errors.parse::<#value_ty>(&var, &value))
+        };
+        loads.extend(quote! {
+            let var = #crate_path::env_var_name(prefix, #name);
+            if let ::core::option::Option::Some(value) = #crate_path::Env::var(env, &var)
+                && let ::core::option::Option::Some(value) = #parse
+            {
+                #crate_path::FieldDelta::delta(
+                    self,
+                    <Self as #crate_path::FieldDelta>::FieldDelta::#variant(value),
+                );
+            }
+        });
+    }
+
+    let generics = get::with_predicates(&item.generics, bounds);
+    let where_clause = &generics.where_clause;
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::FromEnv for #ident #ty_generics
+        #where_clause
+        {
+            fn update_from_env<__E>(
+                &mut self,
+                prefix: &str,
+                env: &__E,
+            ) -> ::core::result::Result<(), #crate_path::FromEnvError>
+            where
+                __E: #crate_path::Env + ?::core::marker::Sized,
+            {
+                let mut errors = #crate_path::FromEnvError::default();
+                #loads
+                errors.into_result()
+            }
+        }
+
+        };
+    })
+}
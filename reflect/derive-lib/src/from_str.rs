@@ -1,85 +1,101 @@
 //! Implementation for `FromStr` derive macro.
 
-use ::std::borrow::Cow;
-
 use ::convert_case::Casing;
+use ::proc_macro2::Span;
 use ::proc_macro2::TokenStream;
 use ::quote::quote;
-use ::rustc_hash::FxHashMap;
-use ::syn::LitStr;
+use ::rustc_hash::{FxHashMap, FxHashSet};
+use ::syn::{Ident, LitStr};
 
-use crate::get::{self, match_parsed_attr};
+use crate::{
+    get::{self, match_parsed_attr},
+    soft_err::push_soft_err,
+};
 
 /// Implement `FromStr` for an enum.
 pub fn from_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
     let mut impl_try_from = false;
     let mut case_convert = false;
+    let mut rename_all = None;
+    let mut accept = None;
+    let mut ignore_case = false;
+    let mut impl_serde = false;
     let crate_path = get::crate_path_and(&item.attrs, &["from_str"], |meta| {
         Ok(match_parsed_attr! {
             meta;
             try_from => impl_try_from = true,
+            serde => impl_serde = true,
             case_convert => :case_convert,
+            rename_all => rename_all = Some(get::list_or_name_value(meta.input, get::case_from_expr)?),
+            ignore_case => :ignore_case,
+            accept => accept = Some(get::list_or_name_value(meta.input, get::cases_from_expr)?),
         })
     })?;
 
     let variants = get::unit_variants(&item)?;
-    let str_rep = get::variants_as_str_reprs(&item)?;
+    let str_rep = get::variants_as_str_reprs(&item, rename_all)?;
 
-    let arms = if !case_convert {
-        str_rep
-            .into_iter()
-            .zip(variants.iter().copied())
-            .collect::<Vec<_>>()
-    } else {
-        let spanned_strings = str_rep
+    let mut names = str_rep
+        .iter()
+        .zip(variants.iter().copied())
+        .map(|(lit_str, variant)| (lit_str.span(), lit_str.value(), variant))
+        .collect::<Vec<_>>();
+    for variant in &item.variants {
+        for alias in get::variant_aliases(variant)? {
+            names.push((alias.span(), alias.value(), &variant.ident));
+        }
+    }
+
+    // Case conversions are expanded to a pattern per spelling, grouped by variant, which
+    // parses faster than normalizing input.
+    let cases = accept.unwrap_or_else(|| {
+        if case_convert {
+            get::CASES.iter().map(|(_, case)| *case).collect()
+        } else {
+            Vec::new()
+        }
+    });
+    let expanded = cases.iter().flat_map(|case| {
+        names
             .iter()
-            .map(|lit_str| (lit_str.span(), lit_str.value()))
-            .collect::<Vec<_>>();
+            .map(|(span, name, variant)| (*span, name.to_case(*case), *variant))
+    });
+    let arms = collect_arms(names.iter().cloned().chain(expanded), |name| {
+        if ignore_case {
+            lowercase(name)
+        } else {
+            name.to_owned()
+        }
+    });
+    let (variants, str_reps) = arms.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
 
-        let arms = ::convert_case::Case::all_cases()
-            .iter()
-            .filter(|case| {
-                matches!(
-                    case,
-                    ::convert_case::Case::Snake
-                        | ::convert_case::Case::Constant
-                        | ::convert_case::Case::UpperSnake
-                        | ::convert_case::Case::Ada
-                        | ::convert_case::Case::Kebab
-                        | ::convert_case::Case::Cobol
-                        | ::convert_case::Case::UpperKebab
-                        | ::convert_case::Case::Train
-                        | ::convert_case::Case::Flat
-                        | ::convert_case::Case::UpperFlat
-                        | ::convert_case::Case::Pascal
-                        | ::convert_case::Case::UpperCamel
-                        | ::convert_case::Case::Camel
-                        | ::convert_case::Case::Lower
-                        | ::convert_case::Case::Upper
-                        | ::convert_case::Case::Title
-                        | ::convert_case::Case::Sentence
-                )
-            })
-            .flat_map(|case| {
-                spanned_strings
-                    .iter()
-                    .zip(variants.iter().copied())
-                    .map(|((span, s), variant)| {
-                        let str_rep = LitStr::new(&s.to_case(*case), *span);
-
-                        (Cow::Owned(str_rep), variant)
-                    })
-            })
-            .collect::<FxHashMap<_, _>>();
-
-        Vec::from_iter(arms)
+    let body = if ignore_case {
+        quote! {
+            #(
+            if #( #crate_path::eq_ignore_case(s, #str_reps) )||* {
+                return Some(Self::#variants);
+            }
+            )*
+            None
+        }
+    } else {
+        quote! {
+            match s {
+                #( #( #str_reps )|* => Some(Self::#variants), )*
+                _ => None,
+            }
+        }
     };
 
-    let arms = arms
-        .iter()
-        .map(|(str_rep, variant)| quote! { #str_rep => Ok(Self::#variant) });
+    let mut expected = Vec::new();
+    for (_, name, _) in &names {
+        if !expected.contains(name) {
+            expected.push(name.clone());
+        }
+    }
 
     let ident = &item.ident;
+    let type_name = ident.to_string();
 
     let try_from = impl_try_from.then(|| {
         quote! {
@@ -96,23 +112,85 @@ pub fn from_str(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
         }
     });
 
+    let deserialize = impl_serde.then(|| {
+        quote! {
+            #[automatically_derived]
+            impl<'__de> #crate_path::__serde::Deserialize<'__de> for #ident {
+                fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: #crate_path::__serde::Deserializer<'__de>,
+                {
+                    #crate_path::deserialize_variant(deserializer)
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         const _:() = {
 
+        #[automatically_derived]
+        impl #crate_path::ParseVariant for #ident {
+            const TYPE_NAME: &'static str = #type_name;
+            const NAMES: &'static [&'static str] = &[#(#expected),*];
+
+            fn parse_variant(s: &str) -> ::core::option::Option<Self> {
+                #body
+            }
+        }
+
         #[automatically_derived]
         impl #crate_path::FromStr for #ident {
             type Err = #crate_path::UnknownVariant;
 
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
-                match s {
-                    #( #arms, )*
-                    _ => Err(#crate_path::UnknownVariant),
-                }
+                <Self as #crate_path::ParseVariant>::parse_variant(s)
+                    .ok_or_else(|| #crate_path::UnknownVariant::new::<Self>(s))
             }
         }
 
         #try_from
+        #deserialize
 
         };
     })
 }
+
+/// Collect match arms from names, grouping names by variant and reporting names of
+/// different variants colliding.
+fn collect_arms<'v>(
+    names: impl IntoIterator<Item = (Span, String, &'v Ident)>,
+    key: impl Fn(&str) -> String,
+) -> Vec<(&'v Ident, Vec<LitStr>)> {
+    let mut arms = Vec::<(&Ident, Vec<LitStr>)>::new();
+    let mut seen = FxHashMap::default();
+    let mut collisions = FxHashSet::default();
+    for (span, name, variant) in names {
+        let key = key(&name);
+        match seen.get(&key) {
+            Some(other) if *other == variant => {}
+            Some(other) => {
+                if collisions.insert((variant, *other)) {
+                    push_soft_err(::syn::Error::new(
+                        span,
+                        format!("{name:?} of {variant} collides with a name of {other}"),
+                    ));
+                }
+            }
+            None => {
+                let lit_str = LitStr::new(&key, span);
+                match arms.iter_mut().find(|(other, _)| *other == variant) {
+                    Some((_, str_reps)) => str_reps.push(lit_str),
+                    None => arms.push((variant, vec![lit_str])),
+                }
+                _ = seen.insert(key, variant);
+            }
+        }
+    }
+    arms
+}
+
+/// Lowercase a string char by char, the same way as `eq_ignore_case`.
+fn lowercase(s: &str) -> String {
+    s.chars().flat_map(char::to_lowercase).collect()
+}
@@ -6,10 +6,12 @@ use ::core::{
 };
 use ::std::borrow::Cow;
 
-use ::proc_macro2::TokenTree;
+use ::convert_case::{Case, Casing};
+use ::proc_macro2::{TokenStream, TokenTree};
+use ::quote::{ToTokens, format_ident, quote};
 use ::syn::{
-    Attribute, Fields, Ident, Token, meta::ParseNestedMeta, parenthesized, parse::ParseStream,
-    parse_quote, parse_quote_spanned,
+    Attribute, Fields, GenericParam, Generics, Ident, Token, WhereClause, meta::ParseNestedMeta,
+    parenthesized, parse::ParseStream, parse_quote, parse_quote_spanned,
 };
 
 use crate::soft_err::push_soft_err;
@@ -131,24 +133,7 @@ pub fn attrs(
                 })?;
 
                 if result.is_continue() {
-                    if meta.input.peek(Token![=]) {
-                        _ = meta.input.parse::<Token![=]>()?;
-                        _ = meta.input.parse::<::syn::Expr>()?;
-                    } else {
-                        meta.input.step(|cursor| {
-                            let mut remainder = *cursor;
-                            while let Some((tt, next)) = remainder.token_tree() {
-                                if let TokenTree::Punct(punct) = tt
-                                    && punct.as_char() == ','
-                                {
-                                    return Ok(((), remainder));
-                                };
-
-                                remainder = next;
-                            }
-                            Ok(((), remainder))
-                        })?;
-                    }
+                    skip_meta(&meta)?;
                 }
 
                 Ok(())
@@ -181,6 +166,65 @@ pub fn attrs(
     Ok(())
 }
 
+/// Skip the value of an attribute meant for other derives.
+///
+/// # Errors
+/// If the value is in `name = value` form and the value is not an expression.
+pub fn skip_meta(meta: &ParseNestedMeta) -> ::syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        _ = meta.input.parse::<Token![=]>()?;
+        _ = meta.input.parse::<::syn::Expr>()?;
+    } else {
+        meta.input.step(|cursor| {
+            let mut remainder = *cursor;
+            while let Some((tt, next)) = remainder.token_tree() {
+                if let TokenTree::Punct(punct) = tt
+                    && punct.as_char() == ','
+                {
+                    return Ok(((), remainder));
+                };
+
+                remainder = next;
+            }
+            Ok(((), remainder))
+        })?;
+    }
+    Ok(())
+}
+
+/// Skip unmatched attributes of the `Fields` derive, which are shared.
+///
+/// # Errors
+/// If the attribute value is malformed.
+pub fn skip_fields_attr(
+    meta: ParsedAttr,
+    result: ControlFlow<()>,
+) -> ::syn::Result<ControlFlow<()>> {
+    if result.is_continue() && meta.name == "fields" {
+        skip_meta(&meta)?;
+        return Ok(ControlFlow::Break(()));
+    }
+    Ok(result)
+}
+
+/// Whether a field is skipped by the `Fields` derive, given the attributes of its struct.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn is_fields_skip(item_attrs: &[Attribute], field_attrs: &[Attribute]) -> ::syn::Result<bool> {
+    let mut skip = false;
+    for attr_list in [item_attrs, field_attrs] {
+        attrs(attr_list, attrl![fields], |meta| {
+            let result = match_parsed_attr! {
+                meta;
+                skip => :skip,
+            };
+            skip_fields_attr(meta, result)
+        })?;
+    }
+    Ok(skip)
+}
+
 /// Get crate_path attribute.
 pub fn crate_path(attrs: &[Attribute], attr_name: &[&str]) -> ::syn::Result<::syn::ExprPath> {
     crate_path_and(attrs, attr_name, |_| Ok(ControlFlow::Continue(())))
@@ -253,10 +297,85 @@ pub fn path_from_expr(requester: &str) -> impl Fn(::syn::Expr) -> ::syn::Result<
     }
 }
 
-/// Get variants as string literals, using as_str attribute if avaialable.
-pub fn variants_as_str_reprs(item: &::syn::ItemEnum) -> ::syn::Result<Vec<Cow<'_, ::syn::LitStr>>> {
+/// Names of supported cases, using serde names where available.
+pub const CASES: &[(&str, Case<'static>)] = &[
+    ("lowercase", Case::Flat),
+    ("UPPERCASE", Case::UpperFlat),
+    ("PascalCase", Case::Pascal),
+    ("camelCase", Case::Camel),
+    ("snake_case", Case::Snake),
+    ("SCREAMING_SNAKE_CASE", Case::Constant),
+    ("kebab-case", Case::Kebab),
+    ("SCREAMING-KEBAB-CASE", Case::Cobol),
+    ("Train-Case", Case::Train),
+    ("Ada_Case", Case::Ada),
+    ("lower case", Case::Lower),
+    ("UPPER CASE", Case::Upper),
+    ("Title Case", Case::Title),
+    ("Sentence case", Case::Sentence),
+];
+
+/// Get a case from a string literal expr.
+pub fn case_from_expr(expr: ::syn::Expr) -> ::syn::Result<Case<'static>> {
+    let ::syn::Expr::Lit(::syn::ExprLit {
+        lit: ::syn::Lit::Str(lit_str),
+        ..
+    }) = expr
+    else {
+        return Err(::syn::Error::new_spanned(
+            expr,
+            "case should be a string literal",
+        ));
+    };
+
+    let value = lit_str.value();
+    CASES
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, case)| *case)
+        .ok_or_else(|| {
+            let names = CASES
+                .iter()
+                .map(|(name, _)| format!("{name:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            ::syn::Error::new_spanned(lit_str, format!("unknown case, expected one of {names}"))
+        })
+}
+
+/// Get a list of cases from an array expr of string literals.
+pub fn cases_from_expr(expr: ::syn::Expr) -> ::syn::Result<Vec<Case<'static>>> {
+    match expr {
+        ::syn::Expr::Array(array) => array.elems.into_iter().map(case_from_expr).collect(),
+        other => case_from_expr(other).map(|case| vec![case]),
+    }
+}
+
+/// Get a string literal from an expr.
+pub fn lit_str_from_expr(requester: &str) -> impl Fn(::syn::Expr) -> ::syn::Result<::syn::LitStr> {
+    move |expr| match expr {
+        ::syn::Expr::Lit(::syn::ExprLit {
+            lit: ::syn::Lit::Str(lit_str),
+            ..
+        }) => Ok(lit_str),
+        other => Err(::syn::Error::new_spanned(
+            other,
+            format!("{requester} expects a string literal"),
+        )),
+    }
+}
+
+/// Get variants as string literals, using as_str attribute if avaialable,
+/// otherwise the variant ident converted to `rename_all` case if given.
+pub fn variants_as_str_reprs<'i>(
+    item: &'i ::syn::ItemEnum,
+    rename_all: Option<Case<'static>>,
+) -> ::syn::Result<Vec<Cow<'i, ::syn::LitStr>>> {
     /// Get variant as a string literal, using as_str attribute if avaialable.
-    fn get_variant_as_str(variant: &::syn::Variant) -> ::syn::Result<Cow<'_, ::syn::LitStr>> {
+    fn get_variant_as_str<'v>(
+        variant: &'v ::syn::Variant,
+        rename_all: Option<Case<'static>>,
+    ) -> ::syn::Result<Cow<'v, ::syn::LitStr>> {
         let mut str_rep = None;
         for attr in &variant.attrs {
             let Some(ident) = attr.path().get_ident() else {
@@ -294,11 +413,67 @@ pub fn variants_as_str_reprs(item: &::syn::ItemEnum) -> ::syn::Result<Vec<Cow<'_
         Ok(if let Some(str_rep) = str_rep {
             str_rep
         } else {
-            let str_rep = variant.ident.to_string();
+            let mut str_rep = variant.ident.to_string();
+            if let Some(case) = rename_all {
+                str_rep = str_rep.to_case(case);
+            }
             Cow::Owned(parse_quote_spanned!(variant.ident.span()=> #str_rep))
         })
     }
-    item.variants.iter().map(get_variant_as_str).collect()
+    item.variants
+        .iter()
+        .map(|variant| get_variant_as_str(variant, rename_all))
+        .collect()
+}
+
+/// Get aliases of a variant given using `from_str(alias = "..")` attributes.
+pub fn variant_aliases(variant: &::syn::Variant) -> ::syn::Result<Vec<::syn::LitStr>> {
+    let mut aliases = Vec::new();
+    attrs(&variant.attrs, attrl![from_str], |meta| {
+        Ok(match_parsed_attr! {
+            meta;
+            alias => aliases.push(list_or_name_value(meta.input, lit_str_from_expr("alias"))?),
+        })
+    })?;
+    Ok(aliases)
+}
+
+/// Get doc comments as paragraphs, with lines trimmed and joined by spaces.
+pub fn doc_paragraphs(attrs: &[Attribute]) -> Vec<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            ::syn::Meta::NameValue(::syn::MetaNameValue {
+                path,
+                value:
+                    ::syn::Expr::Lit(::syn::ExprLit {
+                        lit: ::syn::Lit::Str(lit_str),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("doc") => Some(lit_str.value()),
+            _ => None,
+        })
+        .flat_map(|doc| {
+            // An empty doc comment has no lines but still ends the paragraph.
+            if doc.trim().is_empty() {
+                vec![String::new()]
+            } else {
+                doc.lines().map(|line| line.trim().to_owned()).collect()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    lines
+        .split(String::is_empty)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| paragraph.join(" "))
+        .collect()
+}
+
+/// Get first paragraph of doc comments.
+pub fn doc_string(attrs: &[Attribute]) -> Option<String> {
+    doc_paragraphs(attrs).into_iter().next()
 }
 
 /// Get idents of fields of enum if they are all unit fields.
@@ -318,6 +493,25 @@ pub fn unit_variants(item: &::syn::ItemEnum) -> ::syn::Result<Vec<&Ident>> {
         .collect()
 }
 
+/// Get name of the variant representing a struct field, the field name in pascal case
+/// or `_{i}` for tuple structs.
+pub fn field_variant_name(field: &::syn::Field, i: usize) -> ::syn::Result<Ident> {
+    let Some(ident) = &field.ident else {
+        return Ok(format_ident!("_{i}"));
+    };
+
+    let name = ident.to_string();
+    let mut name = if let Some(ident) = name.strip_prefix("r#") {
+        let name = ident.to_case(Case::Pascal);
+        ::syn::parse_str::<Ident>(&format!("r#{name}"))?
+    } else {
+        ::syn::parse_str::<Ident>(&name.to_case(Case::Pascal))?
+    };
+    name.set_span(ident.span());
+
+    Ok(name)
+}
+
 /// Unwrap a syn type.
 pub fn unwrapped_ty(ty: &::syn::Type) -> &::syn::Type {
     let mut ty = ty;
@@ -332,6 +526,167 @@ pub fn unwrapped_ty(ty: &::syn::Type) -> &::syn::Type {
     }
 }
 
+/// Check if a type is an `Option` by the name of its last path segment.
+pub fn is_option(ty: &::syn::Type) -> bool {
+    matches!(
+        ty,
+        ::syn::Type::Path(ty_path)
+            if ty_path.path.segments.last().is_some_and(|segment| segment.ident == "Option")
+    )
+}
+
+/// Get type of an option type.
+pub fn option_ty(ty: &::syn::Type) -> Option<&::syn::Type> {
+    let ::syn::Type::Path(ty_path) = ty else {
+        push_soft_err(::syn::Error::new_spanned(ty, "expected a type path"));
+        return None;
+    };
+
+    let Some(::syn::PathArguments::AngleBracketed(arguments)) = ty_path
+        .path
+        .segments
+        .last()
+        .map(|segment| &segment.arguments)
+    else {
+        push_soft_err(::syn::Error::new_spanned(
+            ty_path,
+            "expected a type argument",
+        ));
+        return None;
+    };
+
+    let Some(::syn::GenericArgument::Type(ty)) = arguments
+        .args
+        .iter()
+        .find(|arg| matches!(arg, ::syn::GenericArgument::Type(..)))
+    else {
+        push_soft_err(::syn::Error::new_spanned(
+            arguments,
+            "expected at least one type argument",
+        ));
+        return None;
+    };
+
+    Some(ty)
+}
+
+/// Collect idents and lifetime names referenced by tokens.
+fn referenced_names(tokens: TokenStream, idents: &mut Vec<String>, lifetimes: &mut Vec<String>) {
+    let mut is_lifetime = false;
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) => referenced_names(group.stream(), idents, lifetimes),
+            TokenTree::Ident(ident) if is_lifetime => lifetimes.push(ident.to_string()),
+            TokenTree::Ident(ident) => idents.push(ident.to_string()),
+            TokenTree::Punct(punct) => {
+                is_lifetime = punct.as_char() == '\'';
+                continue;
+            }
+            TokenTree::Literal(..) => {}
+        }
+        is_lifetime = false;
+    }
+}
+
+/// Check if a generic param is referenced by collected names.
+fn is_param_referenced(param: &GenericParam, idents: &[String], lifetimes: &[String]) -> bool {
+    match param {
+        GenericParam::Lifetime(param) => lifetimes.contains(&param.lifetime.ident.to_string()),
+        GenericParam::Type(param) => idents.contains(&param.ident.to_string()),
+        GenericParam::Const(param) => idents.contains(&param.ident.to_string()),
+    }
+}
+
+/// Get generics restricted to the params referenced by tokens, such as field types,
+/// including params referenced by bounds of kept params, and where predicates
+/// only referencing kept params.
+pub fn used_generics(generics: &Generics, tokens: impl ToTokens) -> Generics {
+    let mut idents = Vec::new();
+    let mut lifetimes = Vec::new();
+    referenced_names(tokens.into_token_stream(), &mut idents, &mut lifetimes);
+
+    let mut kept = vec![false; generics.params.len()];
+    loop {
+        let mut changed = false;
+        for (param, kept) in generics.params.iter().zip(&mut kept) {
+            if !*kept && is_param_referenced(param, &idents, &lifetimes) {
+                *kept = true;
+                changed = true;
+                referenced_names(param.to_token_stream(), &mut idents, &mut lifetimes);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let is_kept = |tokens: TokenStream| {
+        let mut idents = Vec::new();
+        let mut lifetimes = Vec::new();
+        referenced_names(tokens, &mut idents, &mut lifetimes);
+        generics
+            .params
+            .iter()
+            .zip(&kept)
+            .all(|(param, kept)| *kept || !is_param_referenced(param, &idents, &lifetimes))
+    };
+
+    Generics {
+        params: generics
+            .params
+            .iter()
+            .zip(&kept)
+            .filter(|(_, kept)| **kept)
+            .map(|(param, _)| param.clone())
+            .collect(),
+        where_clause: generics
+            .where_clause
+            .as_ref()
+            .map(|where_clause| WhereClause {
+                where_token: where_clause.where_token,
+                predicates: where_clause
+                    .predicates
+                    .iter()
+                    .filter(|predicate| is_kept(predicate.to_token_stream()))
+                    .cloned()
+                    .collect(),
+            }),
+        ..generics.clone()
+    }
+}
+
+/// Get generic params without bounds or defaults, such that items nested in an impl
+/// may redeclare the generics of the impl, as they may not use outer generics.
+pub fn alias_params(generics: &Generics) -> TokenStream {
+    let params = generics.params.iter().map(|param| match param {
+        ::syn::GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        ::syn::GenericParam::Type(param) => param.ident.to_token_stream(),
+        ::syn::GenericParam::Const(param) => {
+            let ident = &param.ident;
+            let ty = &param.ty;
+            quote!(const #ident: #ty)
+        }
+    });
+    quote!(<#(#params),*>)
+}
+
+/// Get generics with a lifetime param added first.
+pub fn with_lifetime(generics: &Generics, lifetime: &::syn::Lifetime) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!(#lifetime));
+    generics
+}
+
+/// Get generics with where predicates added.
+pub fn with_predicates(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = ::syn::WherePredicate>,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
 /// Get a quick dirty xor hash.
 pub fn xor_hash<T: Hash>(of: T) -> u16 {
     #[derive(Debug, Default)]
@@ -43,13 +43,13 @@ impl ToTokens for MemberRef<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             MemberRef::Ident(ident) => ident.to_tokens(tokens),
-            MemberRef::Idx(idx) => idx.to_tokens(tokens),
+            MemberRef::Idx(idx) => ::syn::Index::from(*idx).to_tokens(tokens),
         }
     }
     fn to_token_stream(&self) -> TokenStream {
         match self {
             MemberRef::Ident(ident) => ident.to_token_stream(),
-            MemberRef::Idx(idx) => idx.to_token_stream(),
+            MemberRef::Idx(idx) => ::syn::Index::from(*idx).to_token_stream(),
         }
     }
     fn into_token_stream(self) -> TokenStream
@@ -58,7 +58,7 @@ impl ToTokens for MemberRef<'_> {
     {
         match self {
             MemberRef::Ident(ident) => ident.into_token_stream(),
-            MemberRef::Idx(idx) => idx.into_token_stream(),
+            MemberRef::Idx(idx) => ::syn::Index::from(idx).into_token_stream(),
         }
     }
 }
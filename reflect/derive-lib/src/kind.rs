@@ -0,0 +1,166 @@
+//! Implementation for `Kind` derive macro.
+
+use ::proc_macro2::TokenStream;
+use ::quote::{format_ident, quote};
+use ::syn::{Fields, parse_quote};
+
+use crate::get::{self, ParsedAttr, match_parsed_attr};
+
+/// Generate a unit kind enum for an enum, with a `kind` method to get it.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn kind(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
+    /// Parse an attribute to be forwarded to the kind enum, as a flag or in `name = value` form.
+    fn forward(meta: &ParsedAttr) -> ::syn::Result<TokenStream> {
+        let path = &meta.path;
+        if meta.input.is_empty() || meta.input.peek(::syn::Token![,]) {
+            return Ok(quote!(#path));
+        }
+        let value = get::list_or_name_value(meta.input, Ok::<::syn::Expr, _>)?;
+        Ok(quote!(#path = #value))
+    }
+
+    let mut kind_name = None;
+    let mut impl_default = false;
+    let mut forwarded = Vec::new();
+    let crate_path = get::crate_path_and(&item.attrs, &["kind"], |meta| {
+        Ok(match_parsed_attr! {
+            meta;
+            name => kind_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("name"))?),
+            default => :impl_default,
+            rename_all => forwarded.push(forward(&meta)?),
+            accept => forwarded.push(forward(&meta)?),
+            ignore_case => forwarded.push(forward(&meta)?),
+            case_convert => forwarded.push(forward(&meta)?),
+            serde => forwarded.push(forward(&meta)?),
+            value_enum => forwarded.push(forward(&meta)?),
+            order => forwarded.push(forward(&meta)?),
+            saturating => forwarded.push(forward(&meta)?),
+        })
+    })?;
+
+    let ident = &item.ident;
+    let vis = &item.vis;
+    let kind_name = kind_name.unwrap_or_else(|| format_ident!("{ident}Kind"));
+    let doc = format!("Kind of [{ident}] without any data.");
+
+    let variants = item
+        .variants
+        .iter()
+        .map(|variant| {
+            let attrs = variant.attrs.iter().filter(|attr| {
+                ["doc", "as_str", "from_str", "cycle"]
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            });
+            let variant_ident = &variant.ident;
+            let doc = (!variant.attrs.iter().any(|attr| attr.path().is_ident("doc"))).then(|| {
+                let doc = format!("Kind of [{ident}::{variant_ident}].");
+                quote!(#[doc = #doc])
+            });
+            quote! {
+                #doc
+                #(#attrs)*
+                #variant_ident
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let variant_pat = item
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            match variant.fields {
+                Fields::Named(..) => parse_quote!(#ident{..}),
+                Fields::Unnamed(..) => parse_quote!(#ident(..)),
+                Fields::Unit => parse_quote!(#ident),
+            }
+        })
+        .collect::<Vec<::syn::Pat>>();
+    let variant_ident = item
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let from_kind = impl_default.then(|| {
+        let construct = item.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.fields {
+                Fields::Named(fields) => {
+                    let fields = fields.named.iter().map(|field| &field.ident);
+                    quote!(Self::#ident { #(#fields: ::core::default::Default::default()),* })
+                }
+                Fields::Unnamed(fields) => {
+                    let fields = fields
+                        .unnamed
+                        .iter()
+                        .map(|_| quote!(::core::default::Default::default()));
+                    quote!(Self::#ident(#(#fields),*))
+                }
+                Fields::Unit => quote!(Self::#ident),
+            }
+        });
+
+        quote! {
+            /// Create a value of given kind using default values for all fields.
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#kind_name> for #ident #ty_generics
+            #where_clause
+            {
+                fn from(value: #kind_name) -> Self {
+                    match value {#(
+                        #kind_name::#variant_ident => #construct,
+                    )*}
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+            #crate_path::Variants,
+            #crate_path::Cycle,
+            #crate_path::AsStr,
+            #crate_path::FromStr,
+        )]
+        #[reflect(crate_path = #crate_path #(, #forwarded)*)]
+        #vis enum #kind_name {
+            #(#variants,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #ident #ty_generics
+        #where_clause
+        {
+            /// Get kind of value.
+            #vis const fn kind(&self) -> #kind_name {
+                match self {#(
+                    Self::#variant_pat => #kind_name::#variant_ident,
+                )*}
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<&#ident #ty_generics> for #kind_name
+        #where_clause
+        {
+            fn from(value: &#ident #ty_generics) -> Self {
+                value.kind()
+            }
+        }
+
+        #from_kind
+    })
+}
@@ -14,6 +14,11 @@ pub fn derive_cycle(tokens: TokenStream) -> TokenStream {
     narrow_item_enum(tokens, "Cycle", cycle::cycle)
 }
 
+/// Implement `PartialVariants` for an enum.
+pub fn derive_partial_variants(tokens: TokenStream) -> TokenStream {
+    narrow_item_enum(tokens, "PartialVariants", cycle::partial_variants)
+}
+
 /// Implement `AsStr` for an enum.
 pub fn derive_as_str(tokens: TokenStream) -> TokenStream {
     narrow_item_enum(tokens, "AsStr", as_str::as_str)
@@ -24,11 +29,46 @@ pub fn derive_from_str(tokens: TokenStream) -> TokenStream {
     narrow_item_enum(tokens, "FromStr", from_str::from_str)
 }
 
+/// Implement `Describe` for an enum.
+pub fn derive_describe(tokens: TokenStream) -> TokenStream {
+    narrow_item_enum(tokens, "Describe", describe::describe)
+}
+
+/// Implement a kind enum for an enum.
+pub fn derive_kind(tokens: TokenStream) -> TokenStream {
+    narrow_item_enum(tokens, "Kind", kind::kind)
+}
+
+/// Implement `Repr` for an enum.
+pub fn derive_repr(tokens: TokenStream) -> TokenStream {
+    narrow_item_enum(tokens, "Repr", repr::repr)
+}
+
 /// Implement `Proxy` for an enum.
 pub fn derive_proxy(tokens: TokenStream) -> TokenStream {
     narrow_item_struct(tokens, "Proxy", proxy::proxy)
 }
 
+/// Implement `Diff` for a struct.
+pub fn derive_diff(tokens: TokenStream) -> TokenStream {
+    narrow_item_struct(tokens, "Diff", diff::diff)
+}
+
+/// Implement `FromEnv` for a struct.
+pub fn derive_from_env(tokens: TokenStream) -> TokenStream {
+    narrow_item_struct(tokens, "FromEnv", from_env::from_env)
+}
+
+/// Implement `Merge` for a struct.
+pub fn derive_merge(tokens: TokenStream) -> TokenStream {
+    narrow_item_struct(tokens, "Merge", merge::merge)
+}
+
+/// Implement `StrFields` for a struct.
+pub fn derive_str_fields(tokens: TokenStream) -> TokenStream {
+    narrow_item_struct(tokens, "StrFields", str_fields::str_fields)
+}
+
 /// Implement `IntoFields` for an enum.
 pub fn derive_fields(tokens: TokenStream) -> TokenStream {
     narrow_item_struct(tokens, "Fields", fields::fields)
@@ -36,12 +76,19 @@ pub fn derive_fields(tokens: TokenStream) -> TokenStream {
 
 mod as_str;
 mod cycle;
+mod describe;
+mod diff;
 mod ext;
 mod fields;
+mod from_env;
 mod from_str;
 mod get;
 mod intermediate;
+mod kind;
+mod merge;
 mod narrow;
 mod proxy;
+mod repr;
 mod soft_err;
+mod str_fields;
 mod variants;
@@ -0,0 +1,124 @@
+//! Implementation for `Merge` derive macro.
+
+use ::proc_macro2::TokenStream;
+use ::quote::{format_ident, quote};
+use ::syn::parse_quote;
+
+use crate::{
+    get::{self, attrl, match_parsed_attr},
+    intermediate::MemberRef,
+};
+
+/// Implement `Merge` for a struct.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn merge(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
+    let mut all_skip = false;
+    let mut all_option = false;
+    let crate_path = get::crate_path_and(&item.attrs, attrl![fields merge], |meta| {
+        let result = match_parsed_attr! {
+            meta;
+            skip => :all_skip,
+            option => :all_option,
+        };
+        get::skip_fields_attr(meta, result)
+    })?;
+
+    let mut bindings = Vec::new();
+    let mut merges = TokenStream::new();
+    let mut is_set_arms = TokenStream::new();
+    let mut bounds = Vec::<::syn::WherePredicate>::new();
+    let (impl_generics, ty_generics, _) = item.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    let ident = &item.ident;
+
+    for (i, field) in item.fields.iter().enumerate() {
+        let ty = get::unwrapped_ty(&field.ty);
+        let mut skip = all_skip;
+        // Option fields are merged unless marked otherwise.
+        let mut option = all_option || get::is_option(ty);
+        let mut flatten = false;
+        get::attrs(&field.attrs, attrl![fields merge], |meta| {
+            let result = match_parsed_attr! {
+                meta;
+                skip => :skip,
+                option => :option,
+                flatten => :flatten,
+            };
+            get::skip_fields_attr(meta, result)
+        })?;
+
+        if skip && get::is_fields_skip(&item.attrs, &field.attrs)? {
+            continue;
+        }
+
+        let member = MemberRef::from_ident_or(field.ident.as_ref(), i);
+        let variant = get::field_variant_name(field, i)?;
+        let binding = format_ident!("__{variant}");
+
+        if skip {
+            // Fields skipped only by `Merge` are kept as non-optional fields are.
+            let pattern = if flatten {
+                quote!(__FieldIdx #turbofish::#variant(..))
+            } else {
+                quote!(__FieldIdx #turbofish::#variant)
+            };
+            is_set_arms.extend(quote!(#pattern => true,));
+            continue;
+        } else if flatten {
+            bounds.push(parse_quote!(#ty: #crate_path::Merge));
+            merges.extend(quote! {
+                #crate_path::Merge::merge_from(&mut self.#member, #binding);
+            });
+            is_set_arms.extend(quote! {
+                __FieldIdx #turbofish::#variant(idx) => #crate_path::Merge::is_set(&self.#member, idx),
+            });
+        } else if option && get::is_option(ty) {
+            merges.extend(quote! {
+                if ::core::option::Option::is_none(&self.#member) {
+                    self.#member = #binding;
+                }
+            });
+            is_set_arms.extend(quote! {
+                __FieldIdx #turbofish::#variant => ::core::option::Option::is_some(&self.#member),
+            });
+        } else {
+            // Non-optional fields are always set, as such they are kept.
+            is_set_arms.extend(quote! {
+                __FieldIdx #turbofish::#variant => true,
+            });
+            continue;
+        }
+
+        bindings.push(quote!(#member: #binding));
+    }
+
+    let alias_params = get::alias_params(&item.generics);
+    let generics = get::with_predicates(&item.generics, bounds);
+    let where_clause = &generics.where_clause;
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Merge for #ident #ty_generics
+        #where_clause
+        {
+            fn merge_from(&mut self, lower: Self) {
+                let Self { #(#bindings,)* .. } = lower;
+                #merges
+            }
+
+            fn is_set(&self, idx: <Self as #crate_path::FieldsIdx>::FieldIdx) -> bool {
+                type __FieldIdx #alias_params = <#ident #ty_generics as #crate_path::FieldsIdx>::FieldIdx;
+
+                match idx {
+                    #is_set_arms
+                }
+            }
+        }
+
+        };
+    })
+}
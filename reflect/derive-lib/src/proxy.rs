@@ -7,7 +7,7 @@ use ::syn::{Ident, parse_quote};
 use crate::{
     ext::ResultExt,
     get::{self, match_parsed_attr},
-    soft_err::push_soft_err,
+    intermediate::MemberRef,
 };
 
 /// Implement `Proxy` for a struct.
@@ -15,88 +15,233 @@ pub fn proxy(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
     let mut all_option = false;
     let mut all_getter = false;
     let mut proxy_debug = false;
+    let mut proxy_display = false;
     let mut proxy_name = None;
     let mut deref_to_proxy = false;
     let mut as_ref_proxy = false;
+    let mut all_mutable = false;
+    let mut resolved_name = None;
     let crate_path = get::crate_path_and(&item.attrs, &["proxy"], |meta| {
         Ok(match_parsed_attr! {
             meta;
             as_ref => :as_ref_proxy,
-            debuf => :proxy_debug,
+            debug => :proxy_debug,
             deref => :deref_to_proxy,
+            display => :proxy_display,
             getter => :all_getter,
+            mutable => :all_mutable,
             option => :all_option,
             proxy_name => proxy_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("proxy_name"))?),
+            resolved => resolved_name = Some(get::list_or_name_value(meta.input, get::ident_from_expr("resolved"))?),
         })
     })?;
 
-    let getters = item
+    let mut is_mutable = all_mutable;
+    let fields = item
         .fields
         .iter()
         .enumerate()
         .map(|(i, field)| {
             let mut is_option = all_option;
             let mut create_getter = all_getter;
+            let mut create_mutators = all_mutable;
+            let mut computed = false;
             let mut default_expr = None;
             let mut some_pattern = None;
+            let mut none_expr = None;
+            let mut fallback = None;
             get::attrs(&field.attrs, &["proxy"], |meta| {
                 Ok(match_parsed_attr! {
                     meta;
                     option => :is_option,
                     getter => :create_getter,
+                    mutable => :create_mutators,
+                    computed => :computed,
                     default => default_expr = Some(get::list_or_name_value(meta.input, Ok)?),
                     some_pattern => some_pattern = Some(get::list_or_name_value(meta.input, get::path_from_expr("some_pattern"))?),
+                    none => none_expr = Some(get::list_or_name_value(meta.input, Ok)?),
+                    fallback => fallback = Some(get::list_or_name_value(meta.input, get::ident_from_expr("fallback"))?),
                 })
             })?;
 
             let ty = get::unwrapped_ty(&field.ty);
-            let doc = field
-                .attrs
-                .iter()
-                .filter(|attr| attr.path().is_ident("doc"));
+            let ident = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| format_ident!("_{i}"));
+            is_mutable |= create_mutators;
 
-            if !create_getter {
-                return Ok(TokenStream::default());
-            }
+            let default = match (fallback, default_expr) {
+                (Some(..), Some(default_expr)) => {
+                    return Err(::syn::Error::new_spanned(
+                        default_expr,
+                        "default may not be combined with fallback",
+                    ));
+                }
+                (Some(fallback), None) => OptionDefault::Fallback(fallback),
+                (None, Some(default_expr @ ::syn::Expr::Closure(..))) => {
+                    OptionDefault::Computed(default_expr)
+                }
+                (None, default_expr) => {
+                    let default_expr = default_expr
+                        .unwrap_or_else(|| parse_quote!(::core::default::Default::default()));
+                    if computed {
+                        OptionDefault::Computed(default_expr)
+                    } else {
+                        OptionDefault::Static(default_expr)
+                    }
+                }
+            };
 
-            if !is_option {
-                return Ok(if let Some(ident) = &field.ident {
-                    emit_no_option(ident, ty, doc, ident)
-                } else {
-                    let ident = ::quote::format_ident!("_{i}");
-                    emit_no_option(&ident, ty, doc, i)
-                });
+            Ok(ProxyField {
+                ident,
+                acc: MemberRef::from_ident_or(field.ident.as_ref(), i),
+                vis: &field.vis,
+                ty,
+                doc: field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("doc"))
+                    .collect(),
+                option_ty: is_option.then(|| get::option_ty(ty)).flatten(),
+                is_option,
+                create_getter,
+                create_mutators,
+                default,
+                some_pattern: some_pattern.unwrap_or_else(|| parse_quote!(Some)),
+                none_expr: none_expr
+                    .unwrap_or_else(|| parse_quote!(::core::option::Option::None)),
+            })
+        })
+        .collect::<::syn::Result<Vec<_>>>()?;
+
+    let mut getters = TokenStream::new();
+    let mut resolved_fields = Vec::new();
+    let mut resolve_values = Vec::new();
+    let mut from_resolved_values = Vec::new();
+    let mut resolve_bounds = Vec::new();
+    let mut debug_fields = Vec::new();
+    let mut display_lines = Vec::new();
+    let mut format_bounds = Vec::new();
+    let mut getter_bounds = Vec::new();
+    for field in &fields {
+        let ProxyField {
+            ident,
+            acc,
+            vis,
+            ty,
+            doc,
+            option_ty,
+            is_option,
+            create_getter,
+            create_mutators,
+            default,
+            some_pattern,
+            none_expr,
+        } = field;
+        let acc = *acc;
+
+        if *create_getter && (!is_option || option_ty.is_some()) {
+            let value_ty = option_ty.unwrap_or(ty);
+            if !get::used_generics(&item.generics, value_ty)
+                .params
+                .is_empty()
+            {
+                format_bounds.push(value_ty);
             }
+            let name = ident.to_string();
+            let name = name.strip_prefix("r#").unwrap_or(&name).to_owned();
+            let is_default = if *is_option {
+                quote!(!::core::matches!(self.0.#acc, #some_pattern(..)))
+            } else {
+                quote!(false)
+            };
+            debug_fields.push(quote! {
+                .field(#name, &#crate_path::ProxyValue::new(&*self.#ident(), #is_default))
+            });
+            display_lines.push(quote! {
+                ::core::writeln!(f, "{} = {}", #name, &*self.#ident())?;
+            });
+        }
+
+        let resolved_ty = match option_ty {
+            Some(ty) if *is_option => ty,
+            _ => ty,
+        };
+        if !get::used_generics(&item.generics, resolved_ty)
+            .params
+            .is_empty()
+        {
+            resolve_bounds.push(quote!(#resolved_ty: ::core::clone::Clone));
+        }
+        let resolved_ident =
+            matches!(item.fields, ::syn::Fields::Named(..)).then(|| quote!(#ident:));
+        resolved_fields.push(quote!(#(#doc)* #vis #resolved_ident #resolved_ty));
+
+        if !is_option {
+            let getter = create_getter.then(|| emit_no_option(ident, ty, doc, acc));
+            let mutators = create_mutators.then(|| emit_no_option_mut(ident, ty, acc));
+            getters.extend(quote!(#getter #mutators));
+            resolve_values.push(quote!(#acc: ::core::clone::Clone::clone(&self.0.#acc)));
+            from_resolved_values.push(quote!(#acc: value.#acc));
+            continue;
+        }
+
+        let Some(ty) = *option_ty else {
+            resolve_values.push(quote!(#acc: ::core::clone::Clone::clone(&self.0.#acc)));
+            from_resolved_values.push(quote!(#acc: value.#acc));
+            continue;
+        };
+        let option = OptionGetter {
+            ty,
+            default,
+            returns_cow: returns_cow(&fields, field, &mut Vec::new())?,
+            some_pattern,
+            crate_path: &crate_path,
+            is_generic: !get::used_generics(&item.generics, ty).params.is_empty(),
+        };
+        if *create_getter {
+            getter_bounds.extend(option.bounds());
+        }
+        resolve_bounds.extend(option.bounds());
 
-            Ok(if let Some(ty) = option_ty(ty) {
-                let default_expr = default_expr
-                    .unwrap_or_else(|| parse_quote!(::core::default::Default::default()));
-                let some_pattern = some_pattern.unwrap_or_else(|| parse_quote!(Some));
-                if let Some(ident) = &field.ident {
-                    emit_option(ident, ty, doc, ident, &default_expr, &some_pattern)
+        // Resolved values use getters when available, such that stored defaults are shared.
+        resolve_values.push(if *create_getter {
+            quote!(#acc: ::core::clone::Clone::clone(&*self.#ident()))
+        } else {
+            let default = option.owned_default(quote!(self));
+            quote! {
+                #acc: if let #some_pattern(value) = &self.0.#acc {
+                    ::core::clone::Clone::clone(value)
                 } else {
-                    let ident = ::quote::format_ident!("_{i}");
-                    emit_option(&ident, ty, doc, i, &default_expr, &some_pattern)
+                    #default
                 }
-            } else {
-                TokenStream::default()
-            })
-        })
-        .collect::<::syn::Result<TokenStream>>()?;
+            }
+        });
+        from_resolved_values.push(quote!(#acc: #some_pattern(value.#acc)));
+
+        let getter = create_getter.then(|| emit_option(ident, doc, acc, &option));
+        let mutators = create_mutators.then(|| emit_option_mut(ident, acc, &option, none_expr));
+        getters.extend(quote!(#getter #mutators));
+    }
 
     let ident = &item.ident;
-    let vis = item.vis;
+    let vis = &item.vis;
+    let generics = &item.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let doc = format!("Proxy object for [{ident}]");
 
+    // Named proxies are emitted outside of the const block such that they may be named by
+    // users, the default hidden proxy is emitted inside of it.
     let mut proxy_name_ = None;
-    let [inner, outer] = proxy_name
+    let [outer, inner] = proxy_name
         .ok_or_else(|| format_ident!("__Proxy"))
         .map_either(|proxy_name| {
             let proxy = quote! {
                 #[doc = #doc]
                 #[repr(transparent)]
                 #[automatically_derived]
-                #vis struct #proxy_name(#ident);
+                #vis struct #proxy_name #generics (#ident #ty_generics) #where_clause;
             };
             proxy_name_ = Some(proxy_name);
             proxy
@@ -107,24 +252,166 @@ pub fn proxy(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
     let deref = deref_to_proxy.then(|| {
         quote! {
             #[automatically_derived]
-            impl ::core::ops::Deref for #ident {
-                type Target = #proxy_name;
+            impl #impl_generics ::core::ops::Deref for #ident #ty_generics
+            #where_clause
+            {
+                type Target = #proxy_name #ty_generics;
 
                 #[inline]
                 fn deref(&self) -> &Self::Target {
-                    <#ident as #crate_path::Proxy>::proxy(self)
+                    <Self as #crate_path::Proxy>::proxy(self)
+                }
+            }
+        }
+    });
+
+    let proxy_mut = is_mutable.then(|| {
+        let deref_mut = deref_to_proxy.then(|| {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics ::core::ops::DerefMut for #ident #ty_generics
+                #where_clause
+                {
+                    #[inline]
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        <Self as #crate_path::ProxyMut>::proxy_mut(self)
+                    }
+                }
+            }
+        });
+        quote! {
+            #deref_mut
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::AsMut<#ident #ty_generics> for #proxy_name #ty_generics
+            #where_clause
+            {
+                #[inline]
+                fn as_mut(&mut self) -> &mut #ident #ty_generics {
+                    &mut self.0
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #crate_path::ProxyMut for #ident #ty_generics
+            #where_clause
+            {
+                fn proxy_mut(&mut self) -> &mut Self::Proxy {
+                    unsafe { &mut *(self as *mut _ as *mut _) }
                 }
             }
         }
     });
 
+    let [resolved_outer, resolved_inner] = resolved_name
+        .map(|resolved_name| {
+            let doc = format!(
+                "Fully resolved [{ident}], with every option field set to its effective value."
+            );
+            let resolved = match item.fields {
+                ::syn::Fields::Named(..) => quote! {
+                    #vis struct #resolved_name #generics #where_clause {
+                        #(#resolved_fields,)*
+                    }
+                },
+                ::syn::Fields::Unnamed(..) => quote! {
+                    #vis struct #resolved_name #generics (#(#resolved_fields,)*) #where_clause;
+                },
+                ::syn::Fields::Unit => quote! {
+                    #vis struct #resolved_name #generics #where_clause;
+                },
+            };
+            let bounds = (!resolve_bounds.is_empty()).then(|| quote!(where #(#resolve_bounds,)*));
+            let resolve_doc = format!(
+                "Get a [{resolved_name}] with every option field set to the value of its getter."
+            );
+            let derive_debug = proxy_debug.then(|| quote!(::core::fmt::Debug,));
+            [
+                quote! {
+                    #[doc = #doc]
+                    #[derive(#derive_debug ::core::clone::Clone)]
+                    #resolved
+                },
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #proxy_name #ty_generics
+                    #where_clause
+                    {
+                        #[doc = #resolve_doc]
+                        pub fn resolve(&self) -> #resolved_name #ty_generics
+                        #bounds
+                        {
+                            #resolved_name {
+                                #(#resolve_values,)*
+                            }
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl #impl_generics ::core::convert::From<#resolved_name #ty_generics> for #ident #ty_generics
+                    #where_clause
+                    {
+                        fn from(value: #resolved_name #ty_generics) -> Self {
+                            Self {
+                                #(#from_resolved_values,)*
+                            }
+                        }
+                    }
+                },
+            ]
+        })
+        .unwrap_or_default();
+
+    let format_impl = |format_trait: TokenStream, body: TokenStream| {
+        let generics = get::with_predicates(
+            generics,
+            format_bounds
+                .iter()
+                .map(|ty| parse_quote!(#ty: #format_trait))
+                .chain(getter_bounds.iter().map(|bounds| parse_quote!(#bounds))),
+        );
+        let where_clause = &generics.where_clause;
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #format_trait for #proxy_name #ty_generics
+            #where_clause
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #body
+                }
+            }
+        }
+    };
+    let name = ident.to_string();
+    let debug = proxy_debug.then(|| {
+        format_impl(
+            quote!(::core::fmt::Debug),
+            quote! {
+                f.debug_struct(#name)
+                    #(#debug_fields)*
+                    .finish()
+            },
+        )
+    });
+    let display = proxy_display.then(|| {
+        format_impl(
+            quote!(::core::fmt::Display),
+            quote! {
+                #(#display_lines)*
+                ::core::result::Result::Ok(())
+            },
+        )
+    });
+
     let as_ref = as_ref_proxy.then(|| {
         quote! {
             #[automatically_derived]
-            impl ::core::convert::AsRef<#proxy_name> for #ident {
+            impl #impl_generics ::core::convert::AsRef<#proxy_name #ty_generics> for #ident #ty_generics
+            #where_clause
+            {
                 #[inline]
-                fn as_ref(&self) -> &#proxy_name {
-                    <#ident as #crate_path::Proxy>::proxy(self)
+                fn as_ref(&self) -> &#proxy_name #ty_generics {
+                    <Self as #crate_path::Proxy>::proxy(self)
                 }
             }
         }
@@ -132,27 +419,38 @@ pub fn proxy(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
 
     Ok(quote! {
         #outer
+        #resolved_outer
         const _: () = {
             #inner
+            #resolved_inner
+            #debug
+            #display
             #deref
             #as_ref
+            #proxy_mut
 
             #[automatically_derived]
-            impl #proxy_name {
+            impl #impl_generics #proxy_name #ty_generics
+            #where_clause
+            {
                 #getters
             }
 
             #[automatically_derived]
-            impl ::core::convert::AsRef<#ident> for #proxy_name {
+            impl #impl_generics ::core::convert::AsRef<#ident #ty_generics> for #proxy_name #ty_generics
+            #where_clause
+            {
                 #[inline]
-                fn as_ref(&self) -> &#ident {
+                fn as_ref(&self) -> &#ident #ty_generics {
                     &self.0
                 }
             }
 
             #[automatically_derived]
-            impl #crate_path::Proxy for #ident {
-                type Proxy = #proxy_name;
+            impl #impl_generics #crate_path::Proxy for #ident #ty_generics
+            #where_clause
+            {
+                type Proxy = #proxy_name #ty_generics;
 
                 fn proxy(&self) -> &Self::Proxy {
                     unsafe { &*(self as *const _ as *const _) }
@@ -162,58 +460,203 @@ pub fn proxy(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
     })
 }
 
-/// Get type of an option type.
-fn option_ty(ty: &::syn::Type) -> Option<&::syn::Type> {
-    let ::syn::Type::Path(ty_path) = ty else {
-        push_soft_err(::syn::Error::new_spanned(ty, "expected a type path"));
-        return None;
-    };
+/// Parsed proxy details of a field.
+struct ProxyField<'a> {
+    /// Name of getter.
+    ident: Ident,
+    /// Member access of field.
+    acc: MemberRef<'a>,
+    /// Visibility of field.
+    vis: &'a ::syn::Visibility,
+    /// Type of field.
+    ty: &'a ::syn::Type,
+    /// Doc attributes of field.
+    doc: Vec<&'a ::syn::Attribute>,
+    /// Type returned by getter of option fields.
+    option_ty: Option<&'a ::syn::Type>,
+    /// If field is an option.
+    is_option: bool,
+    /// If a getter should be created.
+    create_getter: bool,
+    /// If setters should be created.
+    create_mutators: bool,
+    /// Default value of option fields.
+    default: OptionDefault,
+    /// Pattern path matching set values.
+    some_pattern: ::syn::Path,
+    /// Expression for unset values.
+    none_expr: ::syn::Expr,
+}
 
-    let Some(::syn::PathArguments::AngleBracketed(arguments)) = ty_path
-        .path
-        .segments
-        .last()
-        .map(|segment| &segment.arguments)
-    else {
-        push_soft_err(::syn::Error::new_spanned(
-            ty_path,
-            "expected a type argument",
-        ));
-        return None;
-    };
+/// Default value of option fields.
+enum OptionDefault {
+    /// Default is evaluated once and stored in a static.
+    Static(::syn::Expr),
+    /// Default is evaluated on every access, closures are given the proxy.
+    Computed(::syn::Expr),
+    /// Default is the value of another getter.
+    Fallback(Ident),
+}
 
-    let Some(::syn::GenericArgument::Type(ty)) = arguments
-        .args
-        .iter()
-        .find(|arg| matches!(arg, ::syn::GenericArgument::Type(..)))
-    else {
-        push_soft_err(::syn::Error::new_spanned(
-            arguments,
-            "expected at least one type argument",
+/// Check if the getter of a field returns a `Cow`, following fallback chains.
+fn returns_cow<'a>(
+    fields: &'a [ProxyField],
+    field: &'a ProxyField,
+    visited: &mut Vec<&'a Ident>,
+) -> ::syn::Result<bool> {
+    let fallback = match &field.default {
+        OptionDefault::Static(..) => return Ok(false),
+        OptionDefault::Computed(..) => return Ok(true),
+        OptionDefault::Fallback(fallback) => fallback,
+    };
+    visited.push(&field.ident);
+    if visited.contains(&fallback) {
+        return Err(::syn::Error::new_spanned(
+            fallback,
+            "fallback chain should not form a cycle",
+        ));
+    }
+    let Some(target) = fields.iter().find(|target| target.ident == *fallback) else {
+        return Err(::syn::Error::new_spanned(
+            fallback,
+            format!("no field named `{fallback}`"),
         ));
-        return None;
     };
+    if !target.create_getter || (target.is_option && target.option_ty.is_none()) {
+        return Err(::syn::Error::new_spanned(
+            fallback,
+            format!("fallback field `{fallback}` should have a getter"),
+        ));
+    }
+    if target.is_option {
+        returns_cow(fields, target, visited)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Getter details of option fields.
+struct OptionGetter<'a> {
+    /// Type returned by getter.
+    ty: &'a ::syn::Type,
+    /// Default value.
+    default: &'a OptionDefault,
+    /// If getter returns a `Cow` rather than a reference.
+    returns_cow: bool,
+    /// Pattern path matching set values.
+    some_pattern: &'a ::syn::Path,
+    /// Crate path to use.
+    crate_path: &'a ::syn::ExprPath,
+    /// If the type references generics of the struct.
+    is_generic: bool,
+}
+
+impl OptionGetter<'_> {
+    /// Bounds required by getter and setters.
+    fn bounds(&self) -> Option<TokenStream> {
+        let Self {
+            ty,
+            default,
+            returns_cow,
+            is_generic,
+            ..
+        } = *self;
+        if !is_generic {
+            None
+        } else if let OptionDefault::Static(..) = default {
+            Some(quote!(#ty: 'static + ::core::marker::Send + ::core::marker::Sync))
+        } else if returns_cow || matches!(default, OptionDefault::Fallback(..)) {
+            Some(quote!(#ty: ::core::clone::Clone))
+        } else {
+            None
+        }
+    }
 
-    Some(ty)
+    /// Expression evaluating to an owned default value, `this` should be a reference to the
+    /// proxy.
+    fn owned_default(&self, this: TokenStream) -> TokenStream {
+        match self.default {
+            OptionDefault::Static(default_expr) => default_expr.to_token_stream(),
+            OptionDefault::Computed(::syn::Expr::Closure(closure)) => {
+                let body = &closure.body;
+                let binding = match closure.inputs.first() {
+                    Some(pat @ ::syn::Pat::Type(..)) => quote!(let #pat = #this;),
+                    Some(pat) => quote!(let #pat: &Self = #this;),
+                    None => quote!(let _: &Self = #this;),
+                };
+                quote!({ #binding #body })
+            }
+            OptionDefault::Computed(default_expr) => default_expr.to_token_stream(),
+            OptionDefault::Fallback(fallback) => {
+                quote!(::core::clone::Clone::clone(&*Self::#fallback(#this)))
+            }
+        }
+    }
 }
 
 /// Emit getter for option fields.
 fn emit_option(
     ident: &Ident,
-    ty: &::syn::Type,
-    doc: impl Iterator<Item = impl ToTokens>,
+    doc: impl IntoIterator<Item = impl ToTokens>,
     acc: impl ToTokens,
-    default_expr: &::syn::Expr,
-    some_pattern: &::syn::Path,
+    option: &OptionGetter,
 ) -> TokenStream {
+    let OptionGetter {
+        ty,
+        default,
+        returns_cow,
+        some_pattern,
+        crate_path,
+        is_generic,
+    } = *option;
+    let doc = doc.into_iter();
+    let bounds = option.bounds().map(|bounds| quote!(where #bounds));
+
+    let (ret, value, default) = match default {
+        // Statics may not use generics, as such defaults of generic types are stored by type.
+        OptionDefault::Static(default_expr) if is_generic => (
+            quote!(&#ty),
+            quote!(value),
+            quote! {
+                struct __DefaultKey;
+                #crate_path::generic_default::<__DefaultKey, _>(|| #default_expr)
+            },
+        ),
+        OptionDefault::Static(default_expr) => (
+            quote!(&#ty),
+            quote!(value),
+            quote! {
+                static DEFAULT: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+                DEFAULT.get_or_init(|| #default_expr)
+            },
+        ),
+        OptionDefault::Computed(..) => {
+            let default = option.owned_default(quote!(self));
+            (
+                quote!(::std::borrow::Cow<'_, #ty>),
+                quote!(::std::borrow::Cow::Borrowed(value)),
+                quote!(::std::borrow::Cow::Owned(#default)),
+            )
+        }
+        OptionDefault::Fallback(fallback) if returns_cow => (
+            quote!(::std::borrow::Cow<'_, #ty>),
+            quote!(::std::borrow::Cow::Borrowed(value)),
+            quote!(self.#fallback()),
+        ),
+        OptionDefault::Fallback(fallback) => {
+            (quote!(&#ty), quote!(value), quote!(self.#fallback()))
+        }
+    };
+
     quote! {
         #(#doc)*
-        pub fn #ident(&self) -> &#ty {
-            static DEFAULT: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+        pub fn #ident(&self) -> #ret
+        #bounds
+        {
             if let #some_pattern(value) = &self.0.#acc {
-                value
+                #value
             } else {
-                DEFAULT.get_or_init(|| #default_expr)
+                #default
             }
         }
     }
@@ -223,9 +666,10 @@ fn emit_option(
 fn emit_no_option(
     ident: &Ident,
     ty: &::syn::Type,
-    doc: impl Iterator<Item = impl ToTokens>,
+    doc: impl IntoIterator<Item = impl ToTokens>,
     acc: impl ToTokens,
 ) -> TokenStream {
+    let doc = doc.into_iter();
     quote! {
         #(#doc)*
         pub fn #ident(&self) -> &#ty {
@@ -233,3 +677,86 @@ fn emit_no_option(
         }
     }
 }
+
+/// Emit setters for option fields.
+fn emit_option_mut(
+    ident: &Ident,
+    acc: impl ToTokens,
+    option: &OptionGetter,
+    none_expr: &::syn::Expr,
+) -> TokenStream {
+    let OptionGetter {
+        ty,
+        default,
+        some_pattern,
+        ..
+    } = *option;
+    let default_expr = option.owned_default(quote!(&*self));
+    let bounds = match default {
+        OptionDefault::Static(..) => None,
+        _ => option.bounds().map(|bounds| quote!(where #bounds)),
+    };
+    let set = format_ident!("set_{ident}");
+    let reset = format_ident!("reset_{ident}");
+    let is_default = format_ident!("is_{ident}_default");
+    let get_mut = format_ident!("{ident}_mut");
+    let set_doc = format!("Set the value of [{ident}][Self::{ident}].");
+    let reset_doc = format!("Unset [{ident}][Self::{ident}] such that the default value is used.");
+    let is_default_doc =
+        format!("Check if [{ident}][Self::{ident}] is not set and uses the default value.");
+    let get_mut_doc = format!(
+        "Get a mutable reference to [{ident}][Self::{ident}], setting it to the default value if not set."
+    );
+
+    quote! {
+        #[doc = #set_doc]
+        pub fn #set(&mut self, value: #ty) {
+            self.0.#acc = #some_pattern(value);
+        }
+
+        #[doc = #reset_doc]
+        pub fn #reset(&mut self) {
+            self.0.#acc = #none_expr;
+        }
+
+        #[doc = #is_default_doc]
+        pub fn #is_default(&self) -> bool {
+            !::core::matches!(self.0.#acc, #some_pattern(..))
+        }
+
+        #[doc = #get_mut_doc]
+        pub fn #get_mut(&mut self) -> &mut #ty
+        #bounds
+        {
+            if !::core::matches!(self.0.#acc, #some_pattern(..)) {
+                let value = #default_expr;
+                self.0.#acc = #some_pattern(value);
+            }
+            if let #some_pattern(value) = &mut self.0.#acc {
+                value
+            } else {
+                ::core::unreachable!("field should have been set to the default value")
+            }
+        }
+    }
+}
+
+/// Emit setters for non-option fields.
+fn emit_no_option_mut(ident: &Ident, ty: &::syn::Type, acc: impl ToTokens) -> TokenStream {
+    let set = format_ident!("set_{ident}");
+    let get_mut = format_ident!("{ident}_mut");
+    let set_doc = format!("Set the value of [{ident}][Self::{ident}].");
+    let get_mut_doc = format!("Get a mutable reference to [{ident}][Self::{ident}].");
+
+    quote! {
+        #[doc = #set_doc]
+        pub fn #set(&mut self, value: #ty) {
+            self.0.#acc = value;
+        }
+
+        #[doc = #get_mut_doc]
+        pub fn #get_mut(&mut self) -> &mut #ty {
+            &mut self.0.#acc
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Implementation for `Repr` derive macro.
+
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::syn::{Fields, Ident};
+
+use crate::get::{self, attrl};
+
+/// Integer types which may be used as enum representations.
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Get integer type of `repr` attribute.
+fn int_repr(item: &::syn::ItemEnum) -> ::syn::Result<Ident> {
+    let mut int_repr = None;
+    for attr in &item.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                if let Some(ident) = meta.path.get_ident()
+                    && INT_REPRS.iter().any(|repr| ident == repr)
+                {
+                    int_repr = Some(ident.clone());
+                } else if meta.input.peek(::syn::token::Paren) {
+                    // Skip arguments of reprs such as `align(8)`.
+                    _ = meta.input.parse::<::proc_macro2::Group>()?;
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    int_repr.ok_or_else(|| {
+        ::syn::Error::new_spanned(
+            &item.ident,
+            "Repr may only be derived for enums with an integer repr such as #[repr(u8)]",
+        )
+    })
+}
+
+/// Implement `Repr` for an enum.
+///
+/// # Errors
+/// If the enum contains non-unit variants or has no integer repr.
+pub fn repr(item: ::syn::ItemEnum) -> ::syn::Result<TokenStream> {
+    let crate_path = get::crate_path(&item.attrs, attrl![])?;
+
+    if let Some(variant) = item
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(::syn::Error::new_spanned(
+            &variant.fields,
+            "Repr may only be derived for enums with only unit variants",
+        ));
+    }
+
+    let int_repr = int_repr(&item)?;
+    let variants = item
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+
+    let ident = &item.ident;
+    let vis = &item.vis;
+    let type_name = ident.to_string();
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #ident {
+            /// Get representation of current variant in const contexts.
+            #[inline]
+            #vis const fn to_repr_const(&self) -> #int_repr {
+                match self {#(
+                    Self::#variants => Self::#variants as #int_repr,
+                )*}
+            }
+
+            /// Get variant with the given representation in const contexts.
+            ///
+            /// # Errors
+            /// If no variant has the given representation.
+            #vis const fn from_repr_const(
+                repr: #int_repr,
+            ) -> ::core::result::Result<Self, #crate_path::InvalidRepr<#int_repr>> {
+                #(
+                if repr == Self::#variants as #int_repr {
+                    return ::core::result::Result::Ok(Self::#variants);
+                }
+                )*
+                ::core::result::Result::Err(#crate_path::InvalidRepr::new(repr, #type_name))
+            }
+        }
+
+        #[automatically_derived]
+        impl #crate_path::Repr for #ident {
+            type Repr = #int_repr;
+
+            const REPRS: &'static [#int_repr] = &[#(Self::#variants as #int_repr),*];
+
+            #[inline]
+            fn to_repr(&self) -> #int_repr {
+                self.to_repr_const()
+            }
+
+            #[inline]
+            fn from_repr(
+                repr: #int_repr,
+            ) -> ::core::result::Result<Self, #crate_path::InvalidRepr<#int_repr>> {
+                Self::from_repr_const(repr)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::From<#ident> for #int_repr {
+            #[inline]
+            fn from(value: #ident) -> Self {
+                value.to_repr_const()
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::TryFrom<#int_repr> for #ident {
+            type Error = #crate_path::InvalidRepr<#int_repr>;
+
+            #[inline]
+            fn try_from(value: #int_repr) -> ::core::result::Result<Self, Self::Error> {
+                Self::from_repr_const(value)
+            }
+        }
+
+        };
+    })
+}
@@ -0,0 +1,250 @@
+//! Implementation for `StrFields` derive macro.
+
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::syn::parse_quote;
+
+use crate::{
+    get::{self, attrl, match_parsed_attr},
+    intermediate::MemberRef,
+};
+
+/// How a field is accessed.
+enum Access<'a> {
+    /// Field is read and written using the field type.
+    Value(&'a ::syn::Type),
+    /// Field is an option read and written using the inner type.
+    Option(&'a ::syn::Type),
+    /// Field is accessed using the `StrFields` implementation of the field type.
+    Flatten,
+}
+
+/// Implement `StrFields` for a struct.
+///
+/// # Errors
+/// If attributes are malformed.
+pub fn str_fields(item: ::syn::ItemStruct) -> ::syn::Result<TokenStream> {
+    let mut all_skip = false;
+    let mut all_option = false;
+    let mut all_read_only = false;
+    let crate_path = get::crate_path_and(&item.attrs, attrl![fields str_fields], |meta| {
+        let result = match_parsed_attr! {
+            meta;
+            skip => :all_skip,
+            option => :all_option,
+            read_only => :all_read_only,
+        };
+        get::skip_fields_attr(meta, result)
+    })?;
+
+    let mut skipped = Vec::new();
+    let mut set_arms = TokenStream::new();
+    let mut get_arms = TokenStream::new();
+    let mut flat_arms = TokenStream::new();
+    let mut bounds = Vec::<::syn::WherePredicate>::new();
+
+    let alias_params = get::alias_params(&item.generics);
+    let (impl_generics, ty_generics, _) = item.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    let ident = &item.ident;
+
+    for (i, field) in item.fields.iter().enumerate() {
+        let mut skip = all_skip;
+        let mut option = all_option;
+        let mut read_only = all_read_only;
+        let mut flatten = false;
+        get::attrs(&field.attrs, attrl![fields str_fields], |meta| {
+            let result = match_parsed_attr! {
+                meta;
+                skip => :skip,
+                option => :option,
+                read_only => :read_only,
+                flatten => :flatten,
+            };
+            get::skip_fields_attr(meta, result)
+        })?;
+
+        let member = MemberRef::from_ident_or(field.ident.as_ref(), i);
+        let name = member.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name).to_owned();
+
+        // Fields are named by both their field and variant names, as accepted by the index.
+        let variant = get::field_variant_name(field, i)?;
+        let variant_str = variant.to_string();
+        let names = if name == variant_str {
+            quote!(#name)
+        } else {
+            quote!(#name | #variant_str)
+        };
+
+        if skip && get::is_fields_skip(&item.attrs, &field.attrs)? {
+            skipped.push(names);
+            continue;
+        }
+        if skip {
+            // Fields skipped only by `StrFields` still have an index variant.
+            let pattern = if flatten {
+                quote!(__FieldIdx #turbofish::#variant(..))
+            } else {
+                quote!(__FieldIdx #turbofish::#variant)
+            };
+            let arm = quote! {
+                #pattern => ::core::result::Result::Err(
+                    #crate_path::StrFieldError::Skipped(::std::boxed::Box::from(field)),
+                ),
+            };
+            set_arms.extend(arm.clone());
+            get_arms.extend(arm);
+            continue;
+        }
+
+        let ty = get::unwrapped_ty(&field.ty);
+        let access = if flatten {
+            Access::Flatten
+        } else if option && let Some(inner) = get::option_ty(ty) {
+            Access::Option(inner)
+        } else {
+            Access::Value(ty)
+        };
+
+        let value_ty = match access {
+            Access::Value(ty) | Access::Option(ty) => ty,
+            Access::Flatten => {
+                bounds.push(parse_quote!(#ty: #crate_path::StrFields));
+
+                // Flattened fields are accessed by the remainder of the dotted name.
+                set_arms.extend(quote! {
+                    __FieldIdx #turbofish::#variant(..) => #crate_path::StrFields::set_str(
+                        &mut self.#member,
+                        field.split_once('.').map_or(field, |(_, nested)| nested),
+                        value,
+                    )
+                    .map_err(|err| err.with_field(field)),
+                });
+                get_arms.extend(quote! {
+                    __FieldIdx #turbofish::#variant(..) => #crate_path::StrFields::get_str(
+                        &self.#member,
+                        field.split_once('.').map_or(field, |(_, nested)| nested),
+                    )
+                    .map_err(|err| err.with_field(field)),
+                });
+                flat_arms.extend(quote! {
+                    ::core::option::Option::Some((#names, nested)) => {
+                        match #crate_path::StrFields::get_str(&self.#member, nested) {
+                            ::core::result::Result::Err(#crate_path::StrFieldError::Skipped(..)) => {
+                                #crate_path::StrFieldError::Skipped(::std::boxed::Box::from(field))
+                            }
+                            _ => #crate_path::StrFieldError::UnknownField(err),
+                        }
+                    }
+                });
+                continue;
+            }
+        };
+
+        bounds.push(parse_quote!(#value_ty: ::core::fmt::Display));
+        if read_only {
+            set_arms.extend(quote! {
+                __FieldIdx #turbofish::#variant => ::core::result::Result::Err(
+                    #crate_path::StrFieldError::ReadOnly(::std::boxed::Box::from(field)),
+                ),
+            });
+        } else {
+            bounds.push(parse_quote!(#value_ty: #crate_path::FromStr));
+            bounds.push(parse_quote! {
+                <#value_ty as #crate_path::FromStr>::Err: ::core::convert::Into<
+                    ::std::boxed::Box<dyn ::core::error::Error + ::core::marker::Send + ::core::marker::Sync>,
+                >
+            });
+            let parsed = quote!(#crate_path::parse_str_field::<#value_ty>(field, value)?);
+            let parsed = match access {
+                Access::Option(..) => quote!(::core::option::Option::Some(#parsed)),
+                _ => parsed,
+            };
+            set_arms.extend(quote! {
+                __FieldIdx #turbofish::#variant => {
+                    #crate_path::FieldDelta::delta(
+                        self,
+                        <Self as #crate_path::FieldDelta>::FieldDelta::#variant(#parsed),
+                    );
+                    ::core::result::Result::Ok(())
+                }
+            });
+        }
+
+        get_arms.extend(match access {
+            Access::Option(..) => quote! {
+                __FieldIdx #turbofish::#variant => match &self.#member {
+                    ::core::option::Option::Some(value) => {
+                        ::core::result::Result::Ok(::std::string::ToString::to_string(value))
+                    }
+                    ::core::option::Option::None => ::core::result::Result::Err(
+                        #crate_path::StrFieldError::Unset(::std::boxed::Box::from(field)),
+                    ),
+                },
+            },
+            _ => quote! {
+                __FieldIdx #turbofish::#variant => {
+                    ::core::result::Result::Ok(::std::string::ToString::to_string(&self.#member))
+                }
+            },
+        });
+    }
+
+    let skipped_arm = (!skipped.is_empty()).then(|| {
+        quote! {
+            _ if ::core::matches!(field, #(#skipped)|*) => {
+                #crate_path::StrFieldError::Skipped(::std::boxed::Box::from(field))
+            }
+        }
+    });
+    let parse_idx = quote! {
+        type __FieldIdx #alias_params = <#ident #ty_generics as #crate_path::FieldsIdx>::FieldIdx;
+
+        let idx = match <__FieldIdx #ty_generics as #crate_path::FromStr>::from_str(field) {
+            ::core::result::Result::Ok(idx) => idx,
+            ::core::result::Result::Err(err) => {
+                return ::core::result::Result::Err(match field.split_once('.') {
+                    #flat_arms
+                    #skipped_arm
+                    _ => #crate_path::StrFieldError::UnknownField(err),
+                });
+            }
+        };
+    };
+
+    let generics = get::with_predicates(&item.generics, bounds);
+    let where_clause = &generics.where_clause;
+
+    Ok(quote! {
+        const _: () = {
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::StrFields for #ident #ty_generics
+        #where_clause
+        {
+            fn set_str(
+                &mut self,
+                field: &str,
+                value: &str,
+            ) -> ::core::result::Result<(), #crate_path::StrFieldError> {
+                #parse_idx
+                match idx {
+                    #set_arms
+                }
+            }
+
+            fn get_str(
+                &self,
+                field: &str,
+            ) -> ::core::result::Result<::std::string::String, #crate_path::StrFieldError> {
+                #parse_idx
+                match idx {
+                    #get_arms
+                }
+            }
+        }
+
+        };
+    })
+}
@@ -9,15 +9,42 @@ pub fn derive_variants(item: TokenStream) -> TokenStream {
 }
 
 /// Derive implementation of `Cycle` for an enum.
+///
+/// Variants are cycled in declaration order, variants with the `skip` attribute are excluded
+/// and cycle to the first and last cycled variant. `order = [C, A, B]` sets the cycle order
+/// instead, excluding variants not listed. With the `saturating` attribute the last and first
+/// variants do not wrap around.
 #[proc_macro_derive(Cycle, attributes(cycle, reflect))]
 pub fn derive_cycle(item: TokenStream) -> TokenStream {
     ::katalog_lib_reflect_derive_lib::derive_cycle(item.into()).into()
 }
 
+/// Derive implementation of `PartialVariants` for an enum.
+///
+/// Partial variants are the variants cycled by the `Cycle` derive, using the same `skip`,
+/// `order` and `saturating` attributes. Enums implementing both `Variants` and `Cycle`
+/// already implement `PartialVariants` and may not derive it.
+#[proc_macro_derive(PartialVariants, attributes(cycle, reflect))]
+pub fn derive_partial_variants(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_partial_variants(item.into()).into()
+}
+
 /// Derive implementation of `AsStr` for an enum.
 ///
 /// With the `as_ref` and `display` attributes `AsRef<str>` and `Display`
 /// will also be derived using `AsStr` implementation.
+///
+/// The `rename_all` attribute in `name = value`, or `list(value)` form, converts
+/// variant names without an `as_str` attribute to the given case, using serde case names
+/// such as `"kebab-case"` or `"SCREAMING_SNAKE_CASE"`.
+///
+/// With the `serde` attribute, and the `serde` feature enabled, `Serialize` is
+/// implemented serializing variants as their `as_str` string.
+///
+/// With the `value_enum` attribute, and the `clap` feature enabled, `clap::ValueEnum` is
+/// implemented for enums also deriving `Variants`. Possible values use `as_str` strings, the
+/// first paragraph of doc comments as help and `from_str(alias = "..")` attributes as aliases.
+/// Variants may be hidden from help using `#[reflect(hide)]`.
 #[proc_macro_derive(AsStr, attributes(as_str, reflect))]
 pub fn derive_as_str(item: TokenStream) -> TokenStream {
     ::katalog_lib_reflect_derive_lib::derive_as_str(item.into()).into()
@@ -25,13 +52,70 @@ pub fn derive_as_str(item: TokenStream) -> TokenStream {
 
 /// Derive implementation of `FromStr` for an enum.
 ///
+/// Errors are `UnknownVariant` describing the input and accepted names, `ParseVariant`
+/// is also implemented for parsing without allocating on errors.
+///
 /// With the `try_from` attribute `TryFrom<&str>`
 /// will also be derived using `FromStr` implementation.
+///
+/// `rename_all` sets the canonical form of variant names in the same way as for `AsStr`.
+/// With the `case_convert` attribute the names are additionally accepted in any supported case,
+/// such as `snake_case` or `Title Case`, matching the spellings of each variant in a single
+/// arm. `accept = ["snake_case", ..]` instead only accepts the given alternate cases.
+///
+/// Variants may have additional names using `#[from_str(alias = "name")]`, and with the
+/// `ignore_case` attribute input is compared case-insensitively, together with `case_convert`
+/// against each case of the names. Names of different variants colliding, including case
+/// conversions, is a compile error.
+///
+/// With the `serde` attribute, and the `serde` feature enabled, `Deserialize` is implemented
+/// deserializing variants from strings using `FromStr`.
 #[proc_macro_derive(FromStr, attributes(from_str, reflect))]
 pub fn derive_from_str(item: TokenStream) -> TokenStream {
     ::katalog_lib_reflect_derive_lib::derive_from_str(item.into()).into()
 }
 
+/// Derive implementation of `Describe` for an enum.
+///
+/// Labels default to variant names in title case and may be set using the `label` attribute
+/// in `name = value`, or `list(value)` form. Descriptions are taken from doc comments, with
+/// paragraphs separated by newlines.
+///
+/// Static properties are declared as `#[reflect(prop(icon = "folder", order = 3))]`, values
+/// may be string, integer, float or boolean literals.
+#[proc_macro_derive(Describe, attributes(describe, reflect))]
+pub fn derive_describe(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_describe(item.into()).into()
+}
+
+/// Derive a unit kind enum for an enum with data-carrying variants.
+///
+/// The kind enum derives `Variants`, `Cycle`, `AsStr` and `FromStr`, and a
+/// `kind` method is added to the enum. Doc, `as_str`, `from_str` and `cycle` attributes of
+/// variants are copied to the kind enum, as are the `rename_all`, `accept`, `case_convert`,
+/// `ignore_case`, `serde`, `value_enum`, `order` and `saturating` attributes.
+///
+/// Using `name` as a `list(value)` or `name = value` attribute sets the name of the
+/// kind enum, by default the name of the enum with a `Kind` suffix.
+///
+/// With the `default` attribute `From<Kind>` is implemented for the enum, using
+/// `Default::default()` for all fields.
+#[proc_macro_derive(Kind, attributes(kind, as_str, from_str, cycle, reflect))]
+pub fn derive_kind(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_kind(item.into()).into()
+}
+
+/// Derive implementation of `Repr` for an enum with an integer `repr`.
+///
+/// Representations use the explicit or implicit discriminants of variants, and are aligned with
+/// `Variants::VARIANTS`. Inherent `to_repr_const` and `from_repr_const` methods are generated
+/// for const contexts, and `From<Enum>` for the integer and `TryFrom<integer>` for the enum
+/// are implemented.
+#[proc_macro_derive(Repr, attributes(reflect))]
+pub fn derive_repr(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_repr(item.into()).into()
+}
+
 /// Derive implementation of `proxy` for an enum.
 ///
 /// With the `option` or `no_option` attribute on struct or fields, set either
@@ -40,11 +124,22 @@ pub fn derive_from_str(item: TokenStream) -> TokenStream {
 /// Using the `getter` attribute on the struct or a field will
 /// generate getters for struct fields where option fields get a backing `OnceLock`
 /// for their default values (and as such require the type is sync). `no_getter`
-/// may be used to disable getters for a specific field.
+/// may be used to disable getters for a specific field. Statics may not use generics, as such
+/// getters of generic structs instead look up their default in a global map behind a lock.
 ///
 /// The `default` attribute in `name = value`, or `list(value)` form, can be used to set
 /// an expression to use instead of `Default::default()`.
 ///
+/// If the `default` expression is a closure, or the `computed` attribute is used, the default is
+/// evaluated on every access instead of being stored, and the getter returns a `Cow` (requiring
+/// the type to be `Clone`). This allows defaults of types which are not sync. Closures are given
+/// a reference to the proxy, such that defaults may depend on other fields, as in
+/// `default = |this| this.base_dir().join("cache")`.
+///
+/// The `fallback` attribute in `name = value`, or `list(value)` form, names another field whose
+/// getter is used for the default value. Fallbacks may be chained, and the getter returns a `Cow`
+/// if any getter in the chain does.
+///
 /// `some_pattern` in `name = value`, or `list(value)` form, replaces the path part of
 /// matching `Some(..)` for option fields. This combined with the way only the first type
 /// argument is used for the returned type may be used to allow types other than `Option`
@@ -53,14 +148,90 @@ pub fn derive_from_str(item: TokenStream) -> TokenStream {
 /// Using `proxy_name` as a `list(value)` or `name = value` attribute exposes the proxy struct
 /// with the given name.
 ///
+/// Using `resolved` as a `list(value)` or `name = value` attribute generates a struct with the
+/// given name where option fields have their unwrapped type, deriving `Clone`, and `Debug` if
+/// the `debug` attribute is used. The proxy gets a `resolve` method cloning the effective value
+/// of every field into it, and the struct is convertible back using `From`.
+///
+/// The `debug` attribute on the struct implements `Debug` for the proxy, printing the value of
+/// every getter and marking values which are defaults with `(default)`. The `display` attribute
+/// implements `Display`, printing every getter as a `key = value` line.
+///
 /// `[no_]deref` and `[no_]as_ref` implement `Deref` and/or `AsRef` with the proxy as target.
+///
+/// Using the `mutable` attribute on the struct or a field will generate `set_<field>` and
+/// `<field>_mut` methods on the proxy, which is then accessible through `ProxyMut`. Option
+/// fields also get `reset_<field>` and `is_<field>_default`, and `<field>_mut` sets the default
+/// value if not set. The `none` attribute in `name = value`, or `list(value)` form, can be used
+/// to set an expression to unset option fields with instead of `None`. With `deref`, `DerefMut`
+/// is also implemented.
 #[proc_macro_derive(Proxy, attributes(proxy, reflect))]
 pub fn derive_proxy(item: TokenStream) -> TokenStream {
     ::katalog_lib_reflect_derive_lib::derive_proxy(item.into()).into()
 }
 
 /// Derive implementation of `into_fields` for an enum.
+///
+/// With the `serde` attribute, and the `serde` feature enabled, the field index enum
+/// implements `Serialize` and `Deserialize` as field names, and field enums implement them as a
+/// map with a single entry of field name and value. Field names match those of serde derives.
+///
+/// With the `flatten` attribute on a field whose type also implements `Fields`, the
+/// variants of the field enums and index enum wrap those of the nested struct, such as
+/// `Window(WindowIdx::Width)`, and the fields of the nested struct are included when
+/// converting into fields.
+///
+/// The index enum implements `AsStr` using variant names, with names of nested fields joined
+/// by a dot, as in `Window.Width`. `FromStr` accepts these names as well as field names, as in
+/// `window.width`.
 #[proc_macro_derive(Fields, attributes(fields, reflect))]
 pub fn derive_fields(item: TokenStream) -> TokenStream {
     ::katalog_lib_reflect_derive_lib::derive_fields(item.into()).into()
 }
+
+/// Derive implementation of `Diff` for a struct also deriving `Fields`.
+///
+/// Fields skipped by `Fields` are not diffed, and fields may additionally be excluded using
+/// `#[diff(skip)]`. Diffed field types must implement `PartialEq` and `Clone`,
+/// and flattened fields must implement `Diff`, their changes being nested.
+#[proc_macro_derive(Diff, attributes(diff, fields, reflect))]
+pub fn derive_diff(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_diff(item.into()).into()
+}
+
+/// Derive implementation of `StrFields` for a struct also deriving `Fields`.
+///
+/// Fields are addressed by names parsed using `FromStr` of the field index enum, being field or
+/// variant names with names of nested fields joined by a dot, as in `window.width`. Field
+/// values are parsed using `FromStr` and set as deltas, and are read using `Display`. Fields
+/// may be made read-only using `#[str_fields(read_only)]`, and fields skipped by `Fields` or by
+/// `#[str_fields(skip)]` are reported as skipped. Option fields, marked by the `option`
+/// attribute, are parsed and displayed using the inner type, and flattened fields must
+/// implement `StrFields`.
+#[proc_macro_derive(StrFields, attributes(str_fields, fields, reflect))]
+pub fn derive_str_fields(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_str_fields(item.into()).into()
+}
+
+/// Derive implementation of `FromEnv` for a struct also deriving `Fields`.
+///
+/// Each field is read from a variable named by the prefix followed by the field name in upper
+/// snake case, and parsed using `FromStr`. `#[from_env(rename = "NAME")]` replaces the field
+/// name verbatim, such that the variable is still prefixed. `Option` fields, and fields marked
+/// by the `option` attribute, are parsed using the inner type and unset by empty values, which
+/// may be disabled using `no_option`. Flattened fields must implement `FromEnv`, and are read
+/// using the field name followed by an underscore as an additional prefix.
+#[proc_macro_derive(FromEnv, attributes(from_env, fields, reflect))]
+pub fn derive_from_env(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_from_env(item.into()).into()
+}
+
+/// Derive implementation of `Merge` for a struct also deriving `Fields`.
+///
+/// `Option` fields are taken from the lower layer if not set, unless marked by `no_option`,
+/// other fields are kept, as are fields skipped using `#[merge(skip)]`. Flattened fields must
+/// implement `Merge` and are merged recursively.
+#[proc_macro_derive(Merge, attributes(merge, fields, reflect))]
+pub fn derive_merge(item: TokenStream) -> TokenStream {
+    ::katalog_lib_reflect_derive_lib::derive_merge(item.into()).into()
+}
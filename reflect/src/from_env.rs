@@ -0,0 +1,183 @@
+//! [FromEnv] trait, [Env] trait and [FromEnvError] impl.
+
+use ::core::{error::Error, fmt::Display, hash::BuildHasher, str::FromStr};
+use ::std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
+
+/// Source of environment variables.
+///
+/// Implemented for maps of strings such that environments may be injected.
+pub trait Env {
+    /// Get value of variable, or none if it is not set.
+    fn var(&self, name: &str) -> Option<Cow<'_, str>>;
+}
+
+/// Environment of the current process.
+///
+/// Values which are not valid unicode are converted lossily.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessEnv;
+
+impl Env for ProcessEnv {
+    fn var(&self, name: &str) -> Option<Cow<'_, str>> {
+        ::std::env::var_os(name).map(|value| Cow::Owned(value.to_string_lossy().into_owned()))
+    }
+}
+
+impl<S: BuildHasher> Env for HashMap<String, String, S> {
+    fn var(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.get(name).map(|value| Cow::Borrowed(value.as_str()))
+    }
+}
+
+impl Env for BTreeMap<String, String> {
+    fn var(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.get(name).map(|value| Cow::Borrowed(value.as_str()))
+    }
+}
+
+impl<T: Env + ?Sized> Env for &T {
+    fn var(&self, name: &str) -> Option<Cow<'_, str>> {
+        T::var(self, name)
+    }
+}
+
+/// Trait for structs whose fields may be loaded from environment variables.
+///
+/// Should be derived alongside [Fields][crate::Fields].
+pub trait FromEnv {
+    /// Set fields from variables of `env` named by `prefix` followed by the field name in
+    /// upper snake case. Fields whose variable is not set are left unchanged.
+    ///
+    /// # Errors
+    /// If any variable fails to parse, in which case all other fields are still set.
+    fn update_from_env<E>(&mut self, prefix: &str, env: &E) -> Result<(), FromEnvError>
+    where
+        E: Env + ?Sized;
+
+    /// Create a value from the default, setting fields from variables of `env`.
+    ///
+    /// # Errors
+    /// If any variable fails to parse.
+    fn from_env<E>(prefix: &str, env: &E) -> Result<Self, FromEnvError>
+    where
+        Self: Default,
+        E: Env + ?Sized,
+    {
+        let mut value = Self::default();
+        value.update_from_env(prefix, env)?;
+        Ok(value)
+    }
+}
+
+/// Failure to parse a single environment variable.
+#[derive(Debug)]
+pub struct EnvVarError {
+    /// Name of variable.
+    var: Box<str>,
+    /// Value which failed to parse.
+    value: Box<str>,
+    /// Parse error of field type.
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl EnvVarError {
+    /// Get name of variable.
+    pub const fn var(&self) -> &str {
+        &self.var
+    }
+
+    /// Get value which failed to parse.
+    pub const fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for EnvVarError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(f, "{}={:?}, {}", self.var, self.value, self.source)
+    }
+}
+
+impl Error for EnvVarError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Error returned by [FromEnv] implementations, containing
+/// all variables which failed to parse.
+#[derive(Debug, Default)]
+pub struct FromEnvError {
+    /// Variables which failed to parse.
+    errors: Vec<EnvVarError>,
+}
+
+impl FromEnvError {
+    /// Get variables which failed to parse.
+    pub fn errors(&self) -> &[EnvVarError] {
+        &self.errors
+    }
+
+    /// Convert into variables which failed to parse.
+    pub fn into_errors(self) -> Vec<EnvVarError> {
+        self.errors
+    }
+
+    /// Parse the value of a variable, recording errors.
+    #[doc(hidden)]
+    pub fn parse<T>(&mut self, var: &str, value: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        T::from_str(value)
+            .map_err(|err| {
+                self.errors.push(EnvVarError {
+                    var: Box::from(var),
+                    value: Box::from(value),
+                    source: err.into(),
+                });
+            })
+            .ok()
+    }
+
+    /// Record errors of a nested struct.
+    #[doc(hidden)]
+    pub fn extend(&mut self, result: Result<(), Self>) {
+        if let Err(err) = result {
+            self.errors.extend(err.errors);
+        }
+    }
+
+    /// Get a result which is an error if any errors were recorded.
+    #[doc(hidden)]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for FromEnvError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("invalid environment variables")?;
+        for (i, err) in self.errors.iter().enumerate() {
+            f.write_str(if i == 0 { ": " } else { "; " })?;
+            Display::fmt(err, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FromEnvError {}
+
+/// Get the name of a variable from a prefix and a field name in upper snake case.
+#[doc(hidden)]
+pub fn env_var_name(prefix: &str, name: &str) -> String {
+    [prefix, name].concat()
+}
@@ -1,32 +1,250 @@
 //! Reflection utils.
 
-use ::core::fmt::Display;
+use ::core::any::{Any, TypeId};
+use ::std::{
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
 
 #[doc(inline)]
 pub use ::core::str::FromStr;
 
+pub use from_env::{Env, EnvVarError, FromEnv, FromEnvError, ProcessEnv};
+pub use str_fields::{StrFieldError, StrFields};
+pub use unknown_variant::{ParseVariant, UnknownVariant};
+
+#[doc(hidden)]
+pub use from_env::env_var_name;
+#[doc(hidden)]
+pub use str_fields::parse_str_field;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use ::serde as __serde;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_impl::{deserialize_field_name, deserialize_variant};
+
+#[cfg(feature = "clap")]
+#[doc(hidden)]
+pub use ::clap as __clap;
+
 #[doc(inline)]
 pub use ::katalog_lib_traits::*;
 
 #[doc(inline)]
-pub use ::katalog_lib_reflect_derive::{AsStr, Cycle, Fields, FromStr, Proxy, Variants};
+pub use ::katalog_lib_reflect_derive::{
+    AsStr, Cycle, Describe, Diff, Fields, FromEnv, FromStr, Kind, Merge, PartialVariants, Proxy,
+    Repr, StrFields, Variants,
+};
+
+/// Compare input to an already lowercased string ignoring case,
+/// used by [FromStr] implementations using `ignore_case`.
+#[doc(hidden)]
+pub fn eq_ignore_case(input: &str, lowercase: &str) -> bool {
+    input
+        .chars()
+        .flat_map(char::to_lowercase)
+        .eq(lowercase.chars())
+}
+
+/// Get a lazily initialized value for a getter of a generic [Proxy] derive, as statics
+/// may not use generics. Values are keyed by the type `K`, a marker type unique to the
+/// getter, and the type of the value.
+///
+/// Every call takes a shared lock of a global map and looks up the value, only getters of
+/// generic proxies use this, others use a static for each getter.
+#[doc(hidden)]
+pub fn generic_default<K, T>(init: impl FnOnce() -> T) -> &'static T
+where
+    K: Any,
+    T: Any + Send + Sync,
+{
+    /// Leaked values by key and type.
+    type Defaults = HashMap<(TypeId, TypeId), &'static (dyn Any + Send + Sync)>;
+    static DEFAULTS: OnceLock<RwLock<Defaults>> = OnceLock::new();
+
+    let key = (TypeId::of::<K>(), TypeId::of::<T>());
+    let defaults = DEFAULTS.get_or_init(RwLock::default);
+    let cached = defaults
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+        .copied();
+
+    // The lock is not held during init, as init may use other defaults.
+    let value = cached.unwrap_or_else(|| {
+        let value = Box::leak(Box::new(init()));
+        *defaults
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key)
+            .or_insert(value)
+    });
+
+    value
+        .downcast_ref()
+        .expect("generic default should be keyed by its type")
+}
+
+/// Effective value of a [Proxy] getter, used by derived `Debug` implementations of proxies
+/// to mark values which are defaults.
+#[doc(hidden)]
+pub struct ProxyValue<'a, T: ?Sized> {
+    /// Effective value.
+    value: &'a T,
+    /// If the value is a default.
+    is_default: bool,
+}
+
+impl<'a, T: ?Sized> ProxyValue<'a, T> {
+    /// Create a new value.
+    pub const fn new(value: &'a T, is_default: bool) -> Self {
+        Self { value, is_default }
+    }
+}
+
+impl<T: ?Sized + ::core::fmt::Debug> ::core::fmt::Debug for ProxyValue<'_, T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        ::core::fmt::Debug::fmt(self.value, f)?;
+        if self.is_default {
+            f.write_str(" (default)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Names of fields indexed by a [FieldsIdx::FieldIdx], matching those of serde derives with
+/// names of flattened fields joined by a dot. Implemented by the [Fields] derive, and used by
+/// its serde implementations.
+#[doc(hidden)]
+pub trait FieldName
+where
+    Self: Sized,
+{
+    /// Names of fields in order of [Variants::VARIANTS].
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Names of variants as given by [AsStr] in order of [Variants::VARIANTS], with names of
+    /// flattened fields joined by a dot.
+    const VARIANT_NAMES: &'static [&'static str];
+
+    /// Get the name of the field.
+    fn field_name<'a>(&self) -> &'a str;
+
+    /// Get a field by name, returning none if no field has the name.
+    fn from_field_name(name: &str) -> Option<Self>;
+}
+
+/// Names of a [FieldName] implementation with flattened fields as parts, either a single
+/// name with an empty prefix, or the names of a flattened field prefixed by its name and a dot.
+#[doc(hidden)]
+pub type NameParts = [(&'static str, &'static [&'static str])];
+
+/// Get the amount of names in parts.
+#[doc(hidden)]
+pub const fn names_count(parts: &NameParts) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        count += parts[i].1.len();
+        i += 1;
+    }
+    count
+}
+
+/// Get the length of the names in parts joined by [join_names].
+#[doc(hidden)]
+pub const fn joined_names_len(parts: &NameParts) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let (prefix, names) = parts[i];
+        let mut j = 0;
+        while j < names.len() {
+            len += prefix.len() + names[j].len();
+            j += 1;
+        }
+        i += 1;
+    }
+    len
+}
+
+/// Join the prefixed names in parts at compile time, to be split using [split_names].
+#[doc(hidden)]
+pub const fn join_names<const LEN: usize>(parts: &NameParts) -> [u8; LEN] {
+    /// Copy bytes into joined at an offset, returning the offset after them.
+    const fn copy(joined: &mut [u8], mut offset: usize, bytes: &[u8]) -> usize {
+        let mut i = 0;
+        while i < bytes.len() {
+            joined[offset] = bytes[i];
+            offset += 1;
+            i += 1;
+        }
+        offset
+    }
 
-/// Error returned by [FromStr] implementations
-/// when trying to crate an enum from an unknown variant.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
-pub struct UnknownVariant;
+    let mut joined = [0; LEN];
+    let mut offset = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let (prefix, names) = parts[i];
+        let mut j = 0;
+        while j < names.len() {
+            offset = copy(&mut joined, offset, prefix.as_bytes());
+            offset = copy(&mut joined, offset, names[j].as_bytes());
+            j += 1;
+        }
+        i += 1;
+    }
+    joined
+}
 
-impl Display for UnknownVariant {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("no variant with given name available")
+/// Split names in parts joined by [join_names] into the prefixed names.
+///
+/// # Panics
+/// If joined does not contain names joined from parts, at compile time when used in a const.
+#[doc(hidden)]
+pub const fn split_names<const COUNT: usize>(
+    mut joined: &'static [u8],
+    parts: &NameParts,
+) -> [&'static str; COUNT] {
+    let mut split = [""; COUNT];
+    let mut count = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let (prefix, names) = parts[i];
+        let mut j = 0;
+        while j < names.len() {
+            let (name, rest) = joined.split_at(prefix.len() + names[j].len());
+            let Ok(name) = ::core::str::from_utf8(name) else {
+                panic!("joined names should be split at name boundaries");
+            };
+            split[count] = name;
+            joined = rest;
+            count += 1;
+            j += 1;
+        }
+        i += 1;
     }
+    split
 }
-impl ::core::error::Error for UnknownVariant {}
+
+mod from_env;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod str_fields;
+mod unknown_variant;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use ::pretty_assertions::assert_eq;
+    use ::std::{
+        borrow::Cow,
+        path::{Path, PathBuf},
+    };
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Variants, Cycle, AsStr, FromStr)]
     #[reflect(crate_path = crate, as_ref, display, try_from, case_convert)]
@@ -39,15 +257,43 @@ mod tests {
         Fourth,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, AsStr, FromStr)]
+    #[reflect(crate_path = crate, rename_all = "kebab-case", accept = ["snake_case"], display)]
+    #[cfg_attr(feature = "serde", reflect(serde))]
+    enum RenameTestEnum {
+        DarkMode,
+        #[as_str = "light"]
+        LightMode,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr)]
+    #[reflect(crate_path = crate, ignore_case)]
+    enum IgnoreCaseTestEnum {
+        #[from_str(alias = "old-name", alias = "x")]
+        Straße,
+        Other,
+    }
+
+    #[derive(Debug, PartialEq, Kind)]
+    #[reflect(crate_path = crate, kind(name = KindTestKind, default))]
+    enum KindTestEnum {
+        Empty,
+        #[as_str = "tuple"]
+        Tuple(u32, String),
+        Named {
+            value: Option<i32>,
+        },
+    }
+
     #[derive(Debug, Proxy, Fields)]
-    #[reflect(crate_path = crate, option, getter, debug)]
+    #[reflect(crate_path = crate, option, getter, mutable, debug, display)]
     struct OptDefaultTestStruct {
         first: Option<String>,
         #[reflect(default = 5)]
         second: Option<i32>,
         #[reflect(proxy(no_option))]
         third: u32,
-        #[reflect(some_pattern = Ok)]
+        #[reflect(some_pattern = Ok, none = Err(()))]
         fourth: Result<u8, ()>,
     }
 
@@ -81,6 +327,685 @@ mod tests {
         assert_eq!(*s.proxy().fourth(), 15);
     }
 
+    #[test]
+    fn derived_proxy_format() {
+        let s = OptDefaultTestStruct {
+            first: Some(String::from("Hello")),
+            second: None,
+            third: 7,
+            fourth: Err(()),
+        };
+
+        assert_eq!(
+            format!("{:?}", s.proxy()),
+            r#"OptDefaultTestStruct { first: "Hello", second: 5 (default), third: 7, fourth: 0 (default) }"#
+        );
+        assert_eq!(
+            s.proxy().to_string(),
+            "first = Hello\nsecond = 5\nthird = 7\nfourth = 0\n"
+        );
+    }
+
+    #[test]
+    fn derived_proxy_mut() {
+        let mut s = OptDefaultTestStruct {
+            first: None,
+            second: Some(1),
+            third: 7,
+            fourth: Err(()),
+        };
+
+        assert!(s.proxy().is_first_default());
+        s.proxy_mut().first_mut().push_str("pushed");
+        assert_eq!(s.first.as_deref(), Some("pushed"));
+        assert!(!s.proxy().is_first_default());
+
+        assert!(!s.proxy().is_second_default());
+        s.proxy_mut().reset_second();
+        assert_eq!(s.second, None);
+        *s.proxy_mut().second_mut() += 1;
+        assert_eq!(s.second, Some(6));
+
+        s.proxy_mut().set_third(8);
+        *s.proxy_mut().third_mut() += 1;
+        assert_eq!(s.third, 9);
+
+        let proxy = s.proxy_mut();
+        proxy.set_fourth(3);
+        assert_eq!(*proxy.fourth(), 3);
+        proxy.reset_fourth();
+        assert!(proxy.is_fourth_default());
+        assert_eq!(s.fourth, Err(()));
+    }
+
+    #[derive(Debug, Proxy)]
+    #[proxy(crate_path = crate, getter, option, mutable, resolved = PathTestResolved)]
+    struct PathTestStruct {
+        #[proxy(default = PathBuf::from("/base"))]
+        base_dir: Option<PathBuf>,
+        #[proxy(default = |this| this.base_dir().join("cache"))]
+        cache_dir: Option<PathBuf>,
+        #[proxy(fallback = cache_dir)]
+        temp_dir: Option<PathBuf>,
+        #[proxy(fallback = base_dir)]
+        data_dir: Option<PathBuf>,
+        #[proxy(computed, default = ::std::rc::Rc::from("shared"))]
+        shared: Option<::std::rc::Rc<str>>,
+    }
+
+    #[test]
+    fn derived_proxy_computed_default() {
+        let mut s = PathTestStruct {
+            base_dir: None,
+            cache_dir: None,
+            temp_dir: None,
+            data_dir: None,
+            shared: None,
+        };
+
+        assert_eq!(*s.proxy().cache_dir(), Path::new("/base/cache"));
+        assert_eq!(*s.proxy().temp_dir(), Path::new("/base/cache"));
+        assert_eq!(s.proxy().data_dir(), Path::new("/base"));
+        assert_eq!(&**s.proxy().shared(), "shared");
+
+        s.proxy_mut().set_base_dir(PathBuf::from("/home"));
+        assert_eq!(*s.proxy().temp_dir(), Path::new("/home/cache"));
+        assert_eq!(s.proxy().data_dir(), Path::new("/home"));
+
+        s.proxy_mut().set_cache_dir(PathBuf::from("/cache"));
+        assert!(matches!(s.proxy().cache_dir(), Cow::Borrowed(..)));
+        assert_eq!(*s.proxy().temp_dir(), Path::new("/cache"));
+
+        s.proxy_mut().temp_dir_mut().push("tmp");
+        assert_eq!(s.temp_dir.as_deref(), Some(Path::new("/cache/tmp")));
+        s.proxy_mut().data_dir_mut().push("data");
+        assert_eq!(s.data_dir.as_deref(), Some(Path::new("/home/data")));
+    }
+
+    #[test]
+    fn derived_proxy_resolved() {
+        let s = PathTestStruct {
+            base_dir: Some(PathBuf::from("/home")),
+            cache_dir: None,
+            temp_dir: None,
+            data_dir: Some(PathBuf::from("/data")),
+            shared: None,
+        };
+
+        let PathTestResolved {
+            base_dir,
+            cache_dir,
+            temp_dir,
+            data_dir,
+            shared,
+        } = s.proxy().resolve();
+        assert_eq!(base_dir, Path::new("/home"));
+        assert_eq!(cache_dir, Path::new("/home/cache"));
+        assert_eq!(temp_dir, Path::new("/home/cache"));
+        assert_eq!(data_dir, Path::new("/data"));
+        assert_eq!(&*shared, "shared");
+
+        let s = PathTestStruct::from(s.proxy().resolve());
+        assert_eq!(s.cache_dir.as_deref(), Some(Path::new("/home/cache")));
+        assert_eq!(s.shared.as_deref(), Some("shared"));
+
+        let g = GenericTestStruct::<'_, String, (), 2> {
+            name: "name",
+            value: String::from("value"),
+            values: [1, 2],
+            fallback: None,
+            marker: ::core::marker::PhantomData,
+        };
+        assert_eq!(
+            format!("{:?}", g.proxy()),
+            r#"GenericTestStruct { name: "name", value: "value", values: [1, 2], fallback: "" (default) }"#
+        );
+        let resolved = g.proxy().resolve();
+        assert_eq!(resolved.fallback, "");
+        assert_eq!(resolved.values, [1, 2]);
+        let g = GenericTestStruct::from(resolved);
+        assert_eq!(g.fallback.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn derived_proxy_name() {
+        #[derive(Proxy)]
+        #[reflect(crate_path = crate, getter, proxy(proxy_name = NamedProxyTest))]
+        struct NamedProxyTestStruct {
+            #[proxy(option, default = 3)]
+            count: Option<u32>,
+        }
+
+        fn count(proxy: &NamedProxyTest) -> u32 {
+            *proxy.count()
+        }
+
+        assert_eq!(count(NamedProxyTestStruct { count: None }.proxy()), 3);
+    }
+
+    #[test]
+    fn derived_proxy_generic_defaults() {
+        #[derive(Proxy)]
+        #[reflect(crate_path = crate, getter)]
+        struct GenericDefaultTestStruct<T: From<u8>> {
+            #[proxy(option, default = T::from(1))]
+            first: Option<T>,
+            #[proxy(option, default = T::from(2))]
+            second: Option<T>,
+        }
+
+        let s = GenericDefaultTestStruct::<u32> {
+            first: None,
+            second: None,
+        };
+        assert_eq!(*s.proxy().first(), 1);
+        assert_eq!(*s.proxy().second(), 2);
+
+        let s = GenericDefaultTestStruct::<u64> {
+            first: None,
+            second: Some(5),
+        };
+        assert_eq!(*s.proxy().first(), 1);
+        assert_eq!(*s.proxy().second(), 5);
+    }
+
+    #[derive(Debug, Proxy, Fields)]
+    #[reflect(
+        crate_path = crate,
+        getter,
+        fields_name = GenericTestField,
+        fields_name_ref = GenericTestFieldRef,
+        fields_name_mut = GenericTestFieldMut,
+        fields_idx_name = GenericTestFieldIdx,
+        proxy(resolved = GenericTestResolved, debug),
+    )]
+    struct GenericTestStruct<'a, T: Clone, U, const N: usize>
+    where
+        T: Default,
+    {
+        name: &'a str,
+        value: T,
+        values: [u8; N],
+        #[reflect(proxy(option))]
+        fallback: Option<T>,
+        #[reflect(fields(skip), proxy(no_getter))]
+        marker: ::core::marker::PhantomData<U>,
+    }
+
+    #[derive(Debug, Fields, StrFields)]
+    #[reflect(crate_path = crate, fields_name = GenericTupleTestField)]
+    struct GenericTupleTestStruct<T>(#[reflect(fields(skip))] (), T);
+
+    #[test]
+    fn derived_generic_fields() {
+        let mut s = GenericTestStruct::<'_, String, (), 2> {
+            name: "name",
+            value: String::from("value"),
+            values: [1, 2],
+            fallback: None,
+            marker: ::core::marker::PhantomData,
+        };
+
+        s.delta(GenericTestField::Values([3, 4]));
+        if let GenericTestFieldMut::Value(value) = s.get_mut(GenericTestFieldIdx::Value) {
+            value.push('!');
+        }
+        assert!(matches!(
+            s.get(GenericTestFieldIdx::Values),
+            GenericTestFieldRef::Values([3, 4])
+        ));
+        assert_eq!(
+            s.fields()
+                .iter()
+                .map(|field| *field.as_ref())
+                .collect::<Vec<_>>(),
+            GenericTestFieldIdx::VARIANTS
+        );
+
+        assert_eq!(*s.proxy().name(), "name");
+        assert_eq!(s.proxy().value(), "value!");
+        assert_eq!(s.proxy().values(), &[3, 4]);
+        assert_eq!(s.proxy().fallback(), "");
+        s.delta(GenericTestField::Fallback(Some(String::from("set"))));
+        assert_eq!(s.proxy().fallback(), "set");
+
+        let [name, ..] = s.into_fields();
+        assert!(matches!(name, GenericTestField::Name("name")));
+
+        let mut t = GenericTupleTestStruct((), 2.5);
+        t.delta(GenericTupleTestField::_1(4.0));
+        assert_eq!(t.get_str("1").unwrap(), "4");
+        assert!(matches!(t.get_str("0"), Err(StrFieldError::Skipped(..))));
+        let [field] = t.into_fields();
+        assert!(matches!(field, GenericTupleTestField::_1(4.0)));
+    }
+
+    #[derive(Debug, Clone, PartialEq, Fields, Diff)]
+    #[reflect(
+        crate_path = crate,
+        fields_name = DiffTestField,
+        fields_idx_name = DiffTestFieldIdx
+    )]
+    struct DiffTestStruct {
+        name: String,
+        count: u32,
+        #[diff(skip)]
+        ignored: bool,
+        #[fields(skip)]
+        skipped: f32,
+    }
+
+    #[test]
+    fn derived_diff() {
+        let mut value = DiffTestStruct {
+            name: String::from("old"),
+            count: 1,
+            ignored: false,
+            skipped: 0.0,
+        };
+        let new = DiffTestStruct {
+            name: String::from("new"),
+            count: 2,
+            ignored: true,
+            skipped: 1.0,
+        };
+
+        let patch = Diff::diff(&value, &new);
+        assert_eq!(
+            patch.fields().copied().collect::<Vec<DiffTestFieldIdx>>(),
+            [DiffTestFieldIdx::Name, DiffTestFieldIdx::Count]
+        );
+        assert!(matches!(
+            patch.old_values(),
+            [DiffTestField::Name(name), DiffTestField::Count(1)] if name == "old"
+        ));
+        assert!(Diff::diff(&value, &value).is_empty());
+
+        let mut history = History::new();
+        history.apply(&mut value, patch);
+        assert_eq!(value.name, "new");
+        assert_eq!(value.count, 2);
+        assert!(!value.ignored);
+
+        assert!(history.undo(&mut value));
+        assert_eq!(value.name, "old");
+        assert_eq!(value.count, 1);
+        assert!(!history.undo(&mut value));
+
+        assert!(history.redo(&mut value));
+        assert_eq!(value.name, "new");
+        assert!(!history.can_redo());
+
+        history.record(&mut value, DiffTestStruct { count: 5, ..new });
+        assert!(history.undo(&mut value));
+        assert_eq!(value.count, 2);
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Fields, Diff, StrFields, FromEnv)]
+    #[reflect(
+        crate_path = crate,
+        fields_name = WindowTestField,
+        fields_name_ref = WindowTestFieldRef,
+        fields_name_mut = WindowTestFieldMut,
+        fields_idx_name = WindowTestFieldIdx
+    )]
+    struct WindowTestStruct {
+        width: u32,
+        height: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Fields, Diff)]
+    #[reflect(
+        crate_path = crate,
+        fields_name = FlattenTestField,
+        fields_name_ref = FlattenTestFieldRef,
+        fields_name_mut = FlattenTestFieldMut,
+        fields_idx_name = FlattenTestFieldIdx
+    )]
+    struct FlattenTestStruct {
+        title: String,
+        #[fields(flatten)]
+        window: WindowTestStruct,
+    }
+
+    #[test]
+    fn derived_fields_flatten() {
+        use FlattenTestFieldIdx::{Title, Window};
+
+        let mut value = FlattenTestStruct {
+            title: String::from("title"),
+            window: WindowTestStruct {
+                width: 800,
+                height: 600,
+            },
+        };
+
+        assert_eq!(
+            FlattenTestFieldIdx::VARIANTS,
+            [
+                Title,
+                Window(WindowTestFieldIdx::Width),
+                Window(WindowTestFieldIdx::Height)
+            ]
+        );
+        assert_eq!(Window(WindowTestFieldIdx::Height).index_of(), 2);
+        assert_eq!(Window(WindowTestFieldIdx::Height).cycle_next(), Title);
+        assert_eq!(Window(WindowTestFieldIdx::Width).as_str(), "Window.Width");
+        assert_eq!(WindowTestFieldIdx::Width.as_str(), "Width");
+        assert_eq!(Title.as_str(), "Title");
+        for variant in FlattenTestFieldIdx::VARIANTS {
+            assert_eq!(variant.as_str().parse(), Ok(*variant));
+        }
+        assert_eq!(
+            "window.height".parse(),
+            Ok(Window(WindowTestFieldIdx::Height))
+        );
+        assert_eq!("title".parse(), Ok(Title));
+        assert_eq!("width".parse(), Ok(WindowTestFieldIdx::Width));
+        assert!("window.depth".parse::<FlattenTestFieldIdx>().is_err());
+        assert!("Window".parse::<FlattenTestFieldIdx>().is_err());
+
+        assert!(matches!(
+            value.get(Window(WindowTestFieldIdx::Width)),
+            FlattenTestFieldRef::Window(WindowTestFieldRef::Width(800))
+        ));
+        if let FlattenTestFieldMut::Window(WindowTestFieldMut::Height(height)) =
+            value.get_mut(Window(WindowTestFieldIdx::Height))
+        {
+            *height = 720;
+        }
+        value.delta(FlattenTestField::Window(WindowTestField::Width(1280)));
+        assert_eq!(
+            value.window,
+            WindowTestStruct {
+                width: 1280,
+                height: 720
+            }
+        );
+
+        assert_eq!(
+            value
+                .fields()
+                .iter()
+                .map(|field| *field.as_ref())
+                .collect::<Vec<_>>(),
+            FlattenTestFieldIdx::VARIANTS
+        );
+        assert!(matches!(
+            value.clone().into_fields().as_slice(),
+            [
+                FlattenTestField::Title(_),
+                FlattenTestField::Window(WindowTestField::Width(1280)),
+                FlattenTestField::Window(WindowTestField::Height(720)),
+            ]
+        ));
+
+        let new = FlattenTestStruct {
+            window: WindowTestStruct {
+                width: 640,
+                height: 720,
+            },
+            ..value.clone()
+        };
+        let patch = Diff::diff(&value, &new);
+        assert_eq!(
+            patch
+                .fields()
+                .copied()
+                .collect::<Vec<FlattenTestFieldIdx>>(),
+            [Window(WindowTestFieldIdx::Width)]
+        );
+        patch.apply_owned(&mut value);
+        assert_eq!(value, new);
+    }
+
+    #[derive(Debug, Fields, StrFields)]
+    #[reflect(crate_path = crate)]
+    struct StrFieldsTestStruct {
+        theme: RenameTestEnum,
+        #[fields(option)]
+        scale: Option<f32>,
+        #[str_fields(read_only)]
+        version: u32,
+        #[fields(skip)]
+        #[expect(dead_code)]
+        cache: Vec<u8>,
+        #[str_fields(skip)]
+        token: Vec<u8>,
+        #[fields(flatten)]
+        window: WindowTestStruct,
+    }
+
+    #[test]
+    fn derived_str_fields() {
+        let mut value = StrFieldsTestStruct {
+            theme: RenameTestEnum::LightMode,
+            scale: None,
+            version: 2,
+            cache: Vec::new(),
+            token: Vec::new(),
+            window: WindowTestStruct {
+                width: 800,
+                height: 600,
+            },
+        };
+
+        value.set_str("theme", "dark-mode").unwrap();
+        assert_eq!(value.theme, RenameTestEnum::DarkMode);
+        assert_eq!(value.get_str("theme").unwrap(), "dark-mode");
+
+        assert!(matches!(
+            value.get_str("scale"),
+            Err(StrFieldError::Unset(field)) if &*field == "scale"
+        ));
+        value.set_str("scale", "1.5").unwrap();
+        assert_eq!(value.scale, Some(1.5));
+        assert_eq!(value.get_str("scale").unwrap(), "1.5");
+
+        value.set_str("window.width", "1024").unwrap();
+        assert_eq!(value.window.width, 1024);
+        assert_eq!(value.get_str("window.height").unwrap(), "600");
+        value.set_str("Window.Width", "1280").unwrap();
+        assert_eq!(value.window.width, 1280);
+        assert_eq!(value.get_str("Theme").unwrap(), "dark-mode");
+
+        assert_eq!(value.get_str("version").unwrap(), "2");
+        assert!(matches!(
+            value.set_str("version", "3"),
+            Err(StrFieldError::ReadOnly(field)) if &*field == "version"
+        ));
+        assert!(matches!(
+            value.get_str("cache"),
+            Err(StrFieldError::Skipped(field)) if &*field == "cache"
+        ));
+        assert!(matches!(
+            value.set_str("token", "secret"),
+            Err(StrFieldError::Skipped(field)) if &*field == "token"
+        ));
+        assert!(matches!(
+            value.set_str("themes", "light"),
+            Err(StrFieldError::UnknownField(err)) if err.suggestion() == Some("Theme")
+        ));
+        assert!(matches!(
+            value.get_str("Cache"),
+            Err(StrFieldError::Skipped(field)) if &*field == "Cache"
+        ));
+
+        let err = value.set_str("window.height", "tall").unwrap_err();
+        assert_eq!(err.field(), "window.height");
+        assert_eq!(
+            err.to_string(),
+            "invalid value \"tall\" for field \"window.height\", invalid digit found in string"
+        );
+        assert_eq!(value.window.height, 600);
+    }
+
+    #[derive(Debug, Default, Fields, FromEnv)]
+    #[reflect(crate_path = crate)]
+    struct FromEnvTestStruct {
+        #[fields(option)]
+        theme: Option<RenameTestEnum>,
+        count: u32,
+        #[from_env(rename = "LOG")]
+        log_level: String,
+        max_retries: Option<u32>,
+        #[fields(skip)]
+        skipped: u32,
+        #[fields(flatten)]
+        window: WindowTestStruct,
+    }
+
+    #[test]
+    fn derived_from_env() {
+        let env = |vars: &[(&str, &str)]| {
+            vars.iter()
+                .map(|(var, value)| (String::from(*var), String::from(*value)))
+                .collect::<::std::collections::BTreeMap<_, _>>()
+        };
+
+        let value = FromEnvTestStruct::from_env(
+            "APP_",
+            &env(&[
+                ("APP_THEME", "dark-mode"),
+                ("APP_COUNT", "3"),
+                ("APP_LOG", "debug"),
+                ("APP_MAX_RETRIES", "4"),
+                ("APP_SKIPPED", "5"),
+                ("APP_WINDOW_WIDTH", "800"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(value.theme, Some(RenameTestEnum::DarkMode));
+        assert_eq!(value.count, 3);
+        assert_eq!(value.log_level, "debug");
+        assert_eq!(value.max_retries, Some(4));
+        assert_eq!(value.skipped, 0);
+        assert_eq!(
+            value.window,
+            WindowTestStruct {
+                width: 800,
+                height: 0
+            }
+        );
+
+        let mut value = value;
+        value
+            .update_from_env("APP_", &env(&[("APP_THEME", ""), ("APP_MAX_RETRIES", "")]))
+            .unwrap();
+        assert_eq!(value.theme, None);
+        assert_eq!(value.max_retries, None);
+        assert_eq!(value.count, 3);
+
+        let err = value
+            .update_from_env(
+                "APP_",
+                &env(&[
+                    ("APP_COUNT", "many"),
+                    ("APP_LOG", "trace"),
+                    ("APP_WINDOW_HEIGHT", "-1"),
+                ]),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.errors()
+                .iter()
+                .map(EnvVarError::var)
+                .collect::<Vec<_>>(),
+            ["APP_COUNT", "APP_WINDOW_HEIGHT"]
+        );
+        assert_eq!(value.log_level, "trace");
+        assert_eq!(
+            err.to_string(),
+            "invalid environment variables: APP_COUNT=\"many\", invalid digit found in string; \
+             APP_WINDOW_HEIGHT=\"-1\", invalid digit found in string"
+        );
+    }
+
+    #[derive(Debug, PartialEq, Proxy, Fields, Merge)]
+    #[reflect(
+        crate_path = crate,
+        option,
+        getter,
+        fields_idx_name = MergeTestFieldIdx
+    )]
+    struct MergeTestStruct {
+        theme: Option<String>,
+        #[reflect(default = 2)]
+        scale: Option<u32>,
+        #[reflect(proxy(no_option), fields(flatten))]
+        window: MergeWindowTestStruct,
+    }
+
+    #[derive(Debug, PartialEq, Fields, Merge)]
+    #[reflect(crate_path = crate, fields_idx_name = MergeWindowTestFieldIdx)]
+    struct MergeWindowTestStruct {
+        width: Option<u32>,
+        height: Option<u32>,
+        #[merge(skip)]
+        depth: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum MergeTestLayer {
+        Cli,
+        Env,
+        File,
+    }
+
+    #[test]
+    fn derived_merge() {
+        use MergeTestFieldIdx::{Scale, Theme, Window};
+        use MergeWindowTestFieldIdx::{Depth, Height, Width};
+
+        let layer = |theme: Option<&str>, width, height| MergeTestStruct {
+            theme: theme.map(String::from),
+            scale: None,
+            window: MergeWindowTestStruct {
+                width,
+                height,
+                depth: height,
+            },
+        };
+        let cli = layer(Some("dark"), None, None);
+        let env = layer(Some("light"), Some(800), None);
+        let file = layer(None, Some(640), Some(480));
+
+        assert!(cli.is_set(Theme));
+        assert!(!cli.is_set(Window(Width)));
+        assert!(cli.is_set(Window(Depth)));
+
+        let merged = Merged::new(cli, MergeTestLayer::Cli)
+            .merge(env, MergeTestLayer::Env)
+            .merge(file, MergeTestLayer::File);
+        assert_eq!(
+            merged.value(),
+            &MergeTestStruct {
+                theme: Some(String::from("dark")),
+                scale: None,
+                window: MergeWindowTestStruct {
+                    width: Some(800),
+                    height: Some(480),
+                    depth: None,
+                },
+            }
+        );
+
+        let proxy = merged.proxy();
+        assert_eq!(proxy.theme(), "dark");
+        assert_eq!(proxy.source_of(Theme), Some(&MergeTestLayer::Cli));
+        assert_eq!(*proxy.scale(), 2);
+        assert_eq!(proxy.source_of(Scale), None);
+        assert_eq!(merged.source_of(Window(Width)), Some(&MergeTestLayer::Env));
+        assert_eq!(
+            merged.source_of(Window(Height)),
+            Some(&MergeTestLayer::File)
+        );
+
+        let value = layer(None, None, Some(1)).merge(layer(Some("dark"), None, Some(2)));
+        assert_eq!(value.theme.as_deref(), Some("dark"));
+        assert_eq!(value.window.height, Some(1));
+    }
+
     #[test]
     fn derived_variants() {
         use VariantsTestEnum::*;
@@ -128,6 +1053,462 @@ mod tests {
         assert_eq!(Ok(Second), "Second".parse());
         assert_eq!(Ok(Third), "3:rd".parse());
         assert_eq!(Ok(Fourth), "4".parse());
-        assert_eq!(Err(UnknownVariant), "abc".parse::<VariantsTestEnum>());
+        assert_eq!(
+            Err(UnknownVariant::new::<VariantsTestEnum>("abc")),
+            "abc".parse::<VariantsTestEnum>()
+        );
+    }
+
+    #[test]
+    fn enum_set_algebra() {
+        use VariantsTestEnum::*;
+
+        let a = EnumSet::from_iter([First, Third]);
+        let b = EnumSet::from_iter([Third, Fourth]);
+
+        assert_eq!(
+            (a | b).iter().copied().collect::<Vec<_>>(),
+            [First, Third, Fourth]
+        );
+        assert_eq!((a & b).iter().copied().collect::<Vec<_>>(), [Third]);
+        assert_eq!((a ^ b).iter().copied().collect::<Vec<_>>(), [First, Fourth]);
+        assert_eq!((!a).iter().copied().collect::<Vec<_>>(), [Second, Fourth]);
+        assert_eq!(!EnumSet::<VariantsTestEnum>::all(), EnumSet::empty());
+        assert!(a.contains(&First));
+        assert!(!a.contains(&Second));
+    }
+
+    #[test]
+    fn enum_set_from_str_display() {
+        use VariantsTestEnum::*;
+
+        let set = "4, first,3:rd".parse::<EnumSet<VariantsTestEnum>>();
+        assert_eq!(set, Ok(EnumSet::from_iter([First, Third, Fourth])));
+        assert_eq!(
+            set.map(|set| set.to_string()),
+            Ok("First,3:rd,4".to_owned())
+        );
+        assert_eq!(
+            "First,abc".parse::<EnumSet<VariantsTestEnum>>(),
+            Err(UnknownVariant::new::<VariantsTestEnum>("abc"))
+        );
+        assert_eq!("".parse(), Ok(EnumSet::<VariantsTestEnum>::empty()));
+    }
+
+    #[test]
+    fn derived_kind() {
+        let value = KindTestEnum::Named { value: Some(3) };
+
+        assert_eq!(value.kind(), KindTestKind::Named);
+        assert_eq!(KindTestKind::from(&value).cycle_next(), KindTestKind::Empty);
+        assert_eq!(
+            KindTestKind::VARIANTS
+                .iter()
+                .map(AsStr::as_str)
+                .collect::<Vec<_>>(),
+            ["Empty", "tuple", "Named"]
+        );
+        assert_eq!("tuple".parse(), Ok(KindTestKind::Tuple));
+        assert_eq!(
+            KindTestEnum::from(KindTestKind::Tuple),
+            KindTestEnum::Tuple(0, String::new())
+        );
+        assert_eq!(
+            KindTestEnum::from(KindTestKind::Named),
+            KindTestEnum::Named { value: None }
+        );
+    }
+
+    #[test]
+    fn derived_rename_all() {
+        use RenameTestEnum::*;
+
+        assert_eq!(DarkMode.as_str(), "dark-mode");
+        assert_eq!(LightMode.as_str(), "light");
+        assert_eq!(Ok(DarkMode), "dark-mode".parse());
+        assert_eq!(Ok(DarkMode), "dark_mode".parse());
+        assert_eq!(Ok(LightMode), "light".parse());
+        assert_eq!(
+            Err(UnknownVariant::new::<RenameTestEnum>("DarkMode")),
+            "DarkMode".parse::<RenameTestEnum>()
+        );
+        assert_eq!(
+            Err(UnknownVariant::new::<RenameTestEnum>("DARK_MODE")),
+            "DARK_MODE".parse::<RenameTestEnum>()
+        );
+    }
+
+    #[test]
+    fn derived_from_str_alias_ignore_case() {
+        use IgnoreCaseTestEnum::*;
+
+        assert_eq!(Ok(Straße), "STRAẞE".parse());
+        assert_eq!(Ok(Straße), "Old-Name".parse());
+        assert_eq!(Ok(Straße), "X".parse());
+        assert_eq!(Ok(Other), "oTHER".parse());
+        assert_eq!(
+            Err(UnknownVariant::new::<IgnoreCaseTestEnum>("others")),
+            "others".parse::<IgnoreCaseTestEnum>()
+        );
+    }
+
+    #[test]
+    fn derived_from_str_case_convert_ignore_case() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr)]
+        #[reflect(crate_path = crate, case_convert, ignore_case)]
+        enum CaseConvertIgnoreCaseTestEnum {
+            DarkMode,
+            Light,
+        }
+        use CaseConvertIgnoreCaseTestEnum::*;
+
+        assert_eq!(Ok(DarkMode), "dark_mode".parse());
+        assert_eq!(Ok(DarkMode), "dARK-mODE".parse());
+        assert_eq!(Ok(DarkMode), "DaRkMoDe".parse());
+        assert_eq!(Ok(Light), "lIGHT".parse());
+        assert_eq!(
+            CaseConvertIgnoreCaseTestEnum::parse_variant("dark__mode"),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_variant_message() {
+        let err = "drak-mode".parse::<RenameTestEnum>().unwrap_err();
+
+        assert_eq!(err.input(), "drak-mode");
+        assert_eq!(err.type_name(), "RenameTestEnum");
+        assert_eq!(err.expected(), ["dark-mode", "light"]);
+        assert_eq!(err.suggestion(), Some("dark-mode"));
+        assert_eq!(
+            err.to_string(),
+            r#"unknown variant "drak-mode" of RenameTestEnum, expected one of "dark-mode", "light", did you mean "dark-mode"?"#
+        );
+        assert_eq!(
+            IgnoreCaseTestEnum::NAMES,
+            ["Straße", "Other", "old-name", "x"]
+        );
+        assert_eq!(RenameTestEnum::parse_variant("abc"), None);
+    }
+
+    #[test]
+    fn derived_from_str_case_convert() {
+        use VariantsTestEnum::*;
+
+        assert_eq!(Ok(First), "FIRST".parse());
+        assert_eq!(Ok(Second), "second".parse());
+        assert_eq!(Ok(Third), "3:RD".parse());
+        assert_eq!(Ok(Fourth), "4".parse());
+        assert_eq!(VariantsTestEnum::parse_variant("firsts"), None);
+        assert_eq!(VariantsTestEnum::parse_variant("firstfirst"), None);
+        assert_eq!(VariantsTestEnum::parse_variant("se-cond"), None);
+        assert_eq!(VariantsTestEnum::parse_variant(" 4_"), None);
+        assert_eq!(VariantsTestEnum::parse_variant("fIRST"), None);
+    }
+
+    #[test]
+    fn derived_from_str_case_convert_words() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr)]
+        #[reflect(crate_path = crate, case_convert)]
+        enum CaseConvertTestEnum {
+            DarkMode,
+            AbcDef,
+            Ab,
+        }
+        use CaseConvertTestEnum::*;
+
+        for input in [
+            "dark_mode",
+            "DARK_MODE",
+            "Dark_Mode",
+            "dark-mode",
+            "DARK-MODE",
+            "Dark-Mode",
+            "darkmode",
+            "DARKMODE",
+            "darkMode",
+            "dark mode",
+            "DARK MODE",
+            "Dark Mode",
+            "Dark mode",
+        ] {
+            assert_eq!(Ok(DarkMode), input.parse(), "{input}");
+        }
+        for input in [
+            "dar_kmode",
+            "dark_Mode",
+            "dark-Mode",
+            "dark_mode-",
+            "dark-mode_",
+            "dark__mode",
+            "Darkmode",
+            "DarkMODE",
+            "dark mODE",
+        ] {
+            assert_eq!(CaseConvertTestEnum::parse_variant(input), None, "{input}");
+        }
+        assert_eq!(Ok(AbcDef), "abc_def".parse());
+        assert_eq!(Ok(Ab), "AB".parse());
+        assert_eq!(CaseConvertTestEnum::parse_variant("a_b"), None);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Variants, Cycle)]
+    #[reflect(crate_path = crate, order = [C, A, B])]
+    enum CycleOrderTestEnum {
+        A,
+        B,
+        C,
+        #[cycle(skip)]
+        Hidden,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Cycle, PartialVariants)]
+    #[cycle(crate_path = crate, saturating)]
+    enum CycleSaturatingTestEnum {
+        #[reflect(cycle(skip))]
+        Experimental,
+        Low,
+        High,
+    }
+
+    #[test]
+    fn derived_cycle_order() {
+        use CycleOrderTestEnum::*;
+
+        assert_eq!(C.cycle_next(), A);
+        assert_eq!(A.cycle_next(), B);
+        assert_eq!(B.cycle_next(), C);
+        assert_eq!(C.cycle_prev(), B);
+        assert_eq!(A.cycle_prev(), C);
+        assert_eq!(Hidden.cycle_next(), C);
+        assert_eq!(Hidden.cycle_prev(), B);
+        assert_eq!(CycleOrderTestEnum::VARIANTS, [A, B, C, Hidden]);
+
+        // Variants and Cycle implement partial variants using the cycled variants.
+        assert_eq!(
+            CycleOrderTestEnum::partial_variants()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [C, A, B]
+        );
+        assert_eq!(*B.partial_cycle_next(), C);
+        assert_eq!(*Hidden.partial_cycle_prev(), B);
+        assert_eq!(
+            VariantsTestEnum::partial_variants()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            VariantsTestEnum::VARIANTS
+        );
+    }
+
+    #[test]
+    fn derived_cycle_saturating() {
+        use CycleSaturatingTestEnum::*;
+
+        assert_eq!(Low.cycle_next(), High);
+        assert_eq!(High.cycle_next(), High);
+        assert_eq!(High.cycle_prev(), Low);
+        assert_eq!(Low.cycle_prev(), Low);
+        assert_eq!(Experimental.cycle_next(), Low);
+        assert_eq!(Experimental.cycle_prev(), High);
+
+        // Skipped variants are never cycled to.
+        for value in [Experimental, Low, High] {
+            assert_ne!(value.cycle_next(), Experimental);
+            assert_ne!(value.cycle_prev(), Experimental);
+        }
+
+        assert_eq!(
+            CycleSaturatingTestEnum::partial_variants()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [Low, High]
+        );
+        assert_eq!(*High.partial_cycle_next(), High);
+        assert_eq!(*Low.partial_cycle_prev(), Low);
+        assert_eq!(*Experimental.partial_cycle_next(), Low);
+    }
+
+    #[test]
+    fn derived_describe() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Variants, Describe)]
+        #[reflect(crate_path = crate)]
+        enum DescribeTestEnum {
+            /// Open a folder.
+            ///
+            /// Shown in the sidebar.
+            #[reflect(prop(icon = "folder", order = 3, hidden = false))]
+            OpenFolder,
+            #[describe(label = "Close", prop(order = -1, weight = 0.5))]
+            CloseAll,
+        }
+
+        use DescribeTestEnum::*;
+
+        assert_eq!(OpenFolder.label(), "Open Folder");
+        assert_eq!(
+            OpenFolder.description(),
+            Some("Open a folder.\nShown in the sidebar.")
+        );
+        assert_eq!(OpenFolder.prop("icon"), Some(PropValue::Str("folder")));
+        assert_eq!(OpenFolder.prop("order").and_then(|v| v.as_int()), Some(3));
+        assert_eq!(OpenFolder.prop("hidden"), Some(PropValue::Bool(false)));
+        assert_eq!(CloseAll.label(), "Close");
+        assert_eq!(CloseAll.description(), None);
+        assert_eq!(CloseAll.prop("order"), Some(PropValue::Int(-1)));
+        assert_eq!(CloseAll.prop("weight"), Some(PropValue::Float(0.5)));
+        assert_eq!(CloseAll.prop("icon"), None);
+        assert_eq!(
+            DescribeTestEnum::VARIANTS
+                .iter()
+                .zip(DescribeTestEnum::DESCRIPTIONS)
+                .map(|(variant, description)| (*variant, description.label))
+                .collect::<Vec<_>>(),
+            [(OpenFolder, "Open Folder"), (CloseAll, "Close")]
+        );
+    }
+
+    #[test]
+    fn derived_repr() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Variants, Repr)]
+        #[reflect(crate_path = crate)]
+        #[repr(u8)]
+        enum ReprTestEnum {
+            First = 3,
+            Second,
+            Third = 10,
+        }
+
+        use ReprTestEnum::*;
+
+        const SECOND: u8 = Second.to_repr_const();
+        assert_eq!(SECOND, 4);
+        assert_eq!(u8::from(Third), 10);
+        assert_eq!(ReprTestEnum::from_repr(3), Ok(First));
+        assert_eq!(ReprTestEnum::try_from(4), Ok(Second));
+
+        let err = ReprTestEnum::from_repr(5).unwrap_err();
+        assert_eq!(*err.value(), 5);
+        assert_eq!(err.type_name(), "ReprTestEnum");
+        assert_eq!(
+            err.to_string(),
+            "value 5 does not match any variant of ReprTestEnum"
+        );
+
+        for variant in ReprTestEnum::VARIANTS {
+            assert_eq!(ReprTestEnum::REPRS[variant.index_of()], variant.to_repr());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn derived_serde() {
+        #[derive(Debug, PartialEq, Proxy, Fields, ::serde::Serialize, ::serde::Deserialize)]
+        #[reflect(
+            crate_path = crate,
+            serde,
+            fields_name = SerdeTestField,
+            fields_idx_name = SerdeTestFieldIdx
+        )]
+        struct SerdeTestStruct {
+            mode: RenameTestEnum,
+            count: u32,
+        }
+
+        let value = SerdeTestStruct {
+            mode: RenameTestEnum::DarkMode,
+            count: 3,
+        };
+        let text = ::toml::to_string(&value).unwrap();
+        assert_eq!(text, "mode = \"dark-mode\"\ncount = 3\n");
+        assert_eq!(::toml::from_str::<SerdeTestStruct>(&text), Ok(value));
+
+        let err =
+            ::toml::from_str::<SerdeTestStruct>("mode = \"drak-mode\"\ncount = 3").unwrap_err();
+        assert_eq!(
+            err.message(),
+            "unknown variant `drak-mode`, expected `dark-mode` or `light`"
+        );
+
+        let field = ::toml::from_str::<SerdeTestField>("count = 5").unwrap();
+        assert!(matches!(field, SerdeTestField::Count(5)));
+        assert_eq!(::toml::to_string(&field).unwrap(), "count = 5\n");
+
+        let idx = ::toml::from_str::<::std::collections::BTreeMap<String, SerdeTestFieldIdx>>(
+            "idx = \"count\"",
+        )
+        .unwrap();
+        assert_eq!(idx["idx"], SerdeTestFieldIdx::Count);
+        assert_eq!(::toml::to_string(&idx).unwrap(), "idx = \"count\"\n");
+
+        #[derive(Debug, Fields)]
+        #[reflect(crate_path = crate, serde, fields_name = GenericSerdeTestField)]
+        struct GenericSerdeTestStruct<'a, T> {
+            name: &'a str,
+            value: T,
+        }
+
+        let field = ::toml::from_str::<GenericSerdeTestField<u8>>("value = 5").unwrap();
+        assert!(matches!(field, GenericSerdeTestField::Value(5)));
+        assert_eq!(::toml::to_string(&field).unwrap(), "value = 5\n");
+
+        #[derive(Debug, Fields)]
+        #[reflect(crate_path = crate, serde, fields_idx_name = FlattenSerdeTestFieldIdx)]
+        struct FlattenSerdeTestStruct {
+            #[fields(flatten)]
+            inner: SerdeTestStruct,
+        }
+
+        let idx =
+            ::toml::from_str::<::std::collections::BTreeMap<String, FlattenSerdeTestFieldIdx>>(
+                "idx = \"inner.count\"",
+            )
+            .unwrap();
+        assert_eq!(idx["idx"].as_str(), "Inner.Count");
+        assert_eq!(::toml::to_string(&idx).unwrap(), "idx = \"inner.count\"\n");
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn derived_value_enum() {
+        use ::clap::ValueEnum;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Variants, AsStr, FromStr)]
+        #[reflect(crate_path = crate, rename_all = "kebab-case", value_enum)]
+        enum ValueEnumTest {
+            /// Use a dark theme.
+            ///
+            /// Not part of help.
+            #[from_str(alias = "night")]
+            DarkMode,
+            /// Use a light theme.
+            #[reflect(hide)]
+            LightMode,
+        }
+
+        let dark = ValueEnumTest::DarkMode.to_possible_value().unwrap();
+        assert_eq!(dark.get_name(), "dark-mode");
+        assert_eq!(
+            dark.get_help().map(ToString::to_string).as_deref(),
+            Some("Use a dark theme.")
+        );
+        assert!(!dark.is_hide_set());
+        assert!(
+            ValueEnumTest::LightMode
+                .to_possible_value()
+                .unwrap()
+                .is_hide_set()
+        );
+        assert_eq!(
+            <ValueEnumTest as ValueEnum>::from_str("night", false),
+            Ok(ValueEnumTest::DarkMode)
+        );
+        assert_eq!(
+            <ValueEnumTest as ValueEnum>::from_str("Light-Mode", true),
+            Ok(ValueEnumTest::LightMode)
+        );
     }
 }
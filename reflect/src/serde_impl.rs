@@ -0,0 +1,72 @@
+//! Serde helpers used by derived implementations.
+
+use ::core::{fmt::Formatter, marker::PhantomData};
+
+use ::serde::{
+    Deserializer,
+    de::{Error, Visitor},
+};
+
+use crate::{FieldName, ParseVariant};
+
+/// Deserialize a variant from a string using [ParseVariant], reporting
+/// [ParseVariant::NAMES] as expected variants on errors.
+///
+/// # Errors
+/// If the deserializer does not provide a string or no variant matches it.
+pub fn deserialize_variant<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: ParseVariant,
+    D: Deserializer<'de>,
+{
+    /// Visitor parsing strings as variants.
+    struct VariantVisitor<T>(PhantomData<fn() -> T>);
+
+    impl<T: ParseVariant> Visitor<'_> for VariantVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut Formatter) -> ::core::fmt::Result {
+            write!(formatter, "a variant of {}", T::TYPE_NAME)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            T::parse_variant(v).ok_or_else(|| E::unknown_variant(v, T::NAMES))
+        }
+    }
+
+    deserializer.deserialize_str(VariantVisitor(PhantomData))
+}
+
+/// Deserialize a field index from a string using [FieldName], reporting
+/// [FieldName::FIELD_NAMES] as expected fields on errors.
+///
+/// # Errors
+/// If the deserializer does not provide a string or no field matches it.
+pub fn deserialize_field_name<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FieldName,
+    D: Deserializer<'de>,
+{
+    /// Visitor parsing strings as field names.
+    struct FieldNameVisitor<T>(PhantomData<fn() -> T>);
+
+    impl<T: FieldName> Visitor<'_> for FieldNameVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut Formatter) -> ::core::fmt::Result {
+            formatter.write_str("a field name")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            T::from_field_name(v).ok_or_else(|| E::unknown_field(v, T::FIELD_NAMES))
+        }
+    }
+
+    deserializer.deserialize_str(FieldNameVisitor(PhantomData))
+}
@@ -0,0 +1,120 @@
+//! [StrFields] trait and [StrFieldError] impl.
+
+use ::core::{error::Error, fmt::Display, str::FromStr};
+
+use crate::UnknownVariant;
+
+/// Trait for structs whose fields may be read and written by name using strings,
+/// such as for setting config values from the command line.
+///
+/// Should be derived alongside [Fields][crate::Fields].
+pub trait StrFields {
+    /// Parse value using the [FromStr] implementation of the field type
+    /// and set the field with the given name.
+    ///
+    /// # Errors
+    /// If the field is unknown, skipped, read-only or the value cannot be parsed.
+    fn set_str(&mut self, field: &str, value: &str) -> Result<(), StrFieldError>;
+
+    /// Get the value of the field with the given name using its [Display] implementation.
+    ///
+    /// # Errors
+    /// If the field is unknown, skipped or an unset option field.
+    fn get_str(&self, field: &str) -> Result<String, StrFieldError>;
+}
+
+/// Error returned by [StrFields] implementations.
+#[derive(Debug)]
+pub enum StrFieldError {
+    /// No field has the given name.
+    UnknownField(UnknownVariant),
+    /// Field exists but is skipped.
+    Skipped(Box<str>),
+    /// Field may be read but not set.
+    ReadOnly(Box<str>),
+    /// Option field has no value to get.
+    Unset(Box<str>),
+    /// Value could not be parsed as the field type.
+    Parse {
+        /// Name of field.
+        field: Box<str>,
+        /// Value which failed to parse.
+        value: Box<str>,
+        /// Parse error of field type.
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl StrFieldError {
+    /// Get the name of the field the error is for.
+    pub fn field(&self) -> &str {
+        match self {
+            Self::UnknownField(err) => err.input(),
+            Self::Skipped(field)
+            | Self::ReadOnly(field)
+            | Self::Unset(field)
+            | Self::Parse { field, .. } => field,
+        }
+    }
+
+    /// Replace the name of the field the error is for, used by flattened fields
+    /// to report full names.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn with_field(self, name: &str) -> Self {
+        match self {
+            Self::UnknownField(err) => Self::UnknownField(err),
+            Self::Skipped(..) => Self::Skipped(Box::from(name)),
+            Self::ReadOnly(..) => Self::ReadOnly(Box::from(name)),
+            Self::Unset(..) => Self::Unset(Box::from(name)),
+            Self::Parse { value, source, .. } => Self::Parse {
+                field: Box::from(name),
+                value,
+                source,
+            },
+        }
+    }
+}
+
+impl Display for StrFieldError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::UnknownField(err) => Display::fmt(err, f),
+            Self::Skipped(field) => write!(f, "field {field:?} may not be accessed"),
+            Self::ReadOnly(field) => write!(f, "field {field:?} is read-only"),
+            Self::Unset(field) => write!(f, "field {field:?} is not set"),
+            Self::Parse {
+                field,
+                value,
+                source,
+            } => write!(f, "invalid value {value:?} for field {field:?}, {source}"),
+        }
+    }
+}
+
+impl Error for StrFieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownField(err) => Some(err),
+            Self::Parse { source, .. } => Some(&**source),
+            Self::Skipped(..) | Self::ReadOnly(..) | Self::Unset(..) => None,
+        }
+    }
+}
+
+/// Parse the value of a field, used by [StrFields] implementations.
+///
+/// # Errors
+/// If the value cannot be parsed.
+#[doc(hidden)]
+pub fn parse_str_field<T>(field: &str, value: &str) -> Result<T, StrFieldError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    T::from_str(value).map_err(|err| StrFieldError::Parse {
+        field: Box::from(field),
+        value: Box::from(value),
+        source: err.into(),
+    })
+}
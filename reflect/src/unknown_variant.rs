@@ -0,0 +1,128 @@
+//! [UnknownVariant] impl.
+
+use ::core::fmt::Display;
+
+/// Trait for enums which may be parsed from variant names without allocating,
+/// implemented by the [FromStr][crate::FromStr] derive.
+pub trait ParseVariant
+where
+    Self: Sized,
+{
+    /// Name of type, used in errors.
+    const TYPE_NAME: &'static str;
+
+    /// Accepted names of variants including aliases, not including alternate cases.
+    const NAMES: &'static [&'static str];
+
+    /// Parse a variant, returning none if no variant matches input.
+    fn parse_variant(s: &str) -> Option<Self>;
+}
+
+/// Error returned by [FromStr][crate::FromStr] implementations
+/// when trying to crate an enum from an unknown variant.
+///
+/// For a path that does not allocate on errors use [ParseVariant::parse_variant].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnknownVariant {
+    /// Input that did not match any variant.
+    input: Box<str>,
+    /// Name of type that was parsed.
+    type_name: &'static str,
+    /// Accepted names.
+    expected: &'static [&'static str],
+}
+
+impl UnknownVariant {
+    /// Create an error for input which failed to parse as `T`.
+    #[cold]
+    pub fn new<T: ParseVariant>(input: &str) -> Self {
+        Self {
+            input: Box::from(input),
+            type_name: T::TYPE_NAME,
+            expected: T::NAMES,
+        }
+    }
+
+    /// Get input that did not match any variant.
+    pub const fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Get name of type that was parsed.
+    pub const fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Get accepted names, not including alternate cases.
+    pub const fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+
+    /// Get the accepted name closest to input, if any is close enough.
+    ///
+    /// Names are compared case-insensitively using edit distance, and are
+    /// considered close enough if at most a third of the characters differ.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        let input = self.input.to_lowercase();
+        self.expected
+            .iter()
+            .map(|name| (edit_distance(&input, &name.to_lowercase()), *name))
+            .filter(|(distance, name)| *distance <= (name.chars().count() / 3).max(1))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+    }
+}
+
+impl Display for UnknownVariant {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(
+            f,
+            "unknown variant {:?} of {}, expected one of ",
+            self.input, self.type_name
+        )?;
+        for (i, name) in self.expected.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name:?}")?;
+        }
+        if let Some(suggestion) = self.suggestion() {
+            write!(f, ", did you mean {suggestion:?}?")?;
+        }
+        Ok(())
+    }
+}
+
+impl ::core::error::Error for UnknownVariant {}
+
+/// Get the levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn edit_distance_of_strings() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("dark", ""), 4);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("drak", "dark"), 2);
+    }
+}
@@ -0,0 +1,104 @@
+//! [Describe] trait and related types.
+
+/// Static value of a variant property.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum PropValue {
+    /// String value.
+    Str(&'static str),
+    /// Integer value.
+    Int(i64),
+    /// Float value.
+    Float(f64),
+    /// Boolean value.
+    Bool(bool),
+}
+
+impl PropValue {
+    /// Get value if it is a string.
+    #[inline]
+    pub const fn as_str(&self) -> Option<&'static str> {
+        if let Self::Str(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Get value if it is an integer.
+    #[inline]
+    pub const fn as_int(&self) -> Option<i64> {
+        if let Self::Int(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    /// Get value if it is a number, converting integers.
+    #[inline]
+    pub const fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Get value if it is a boolean.
+    #[inline]
+    pub const fn as_bool(&self) -> Option<bool> {
+        if let Self::Bool(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+}
+
+/// Human-readable metadata of a variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Description {
+    /// Label of variant.
+    pub label: &'static str,
+    /// Description of variant, if any.
+    pub description: Option<&'static str>,
+    /// Static properties of variant as key value pairs.
+    pub props: &'static [(&'static str, PropValue)],
+}
+
+impl Description {
+    /// Get the value of a property.
+    pub fn prop(&self, key: &str) -> Option<PropValue> {
+        self.props
+            .iter()
+            .find_map(|(name, value)| (*name == key).then_some(*value))
+    }
+}
+
+/// Trait for enums providing human-readable metadata for variants.
+pub trait Describe {
+    /// Descriptions of all variants in declaration order,
+    /// as such aligned with [Variants::VARIANTS][crate::Variants::VARIANTS].
+    const DESCRIPTIONS: &'static [Description];
+
+    /// Get description of current variant.
+    fn describe(&self) -> &'static Description;
+
+    /// Get label of current variant.
+    #[inline]
+    fn label(&self) -> &'static str {
+        self.describe().label
+    }
+
+    /// Get description of current variant.
+    #[inline]
+    fn description(&self) -> Option<&'static str> {
+        self.describe().description
+    }
+
+    /// Get a property of current variant.
+    #[inline]
+    fn prop(&self, key: &str) -> Option<PropValue> {
+        self.describe().prop(key)
+    }
+}
@@ -0,0 +1,264 @@
+//! [Diff] trait, [Patch] and [History] impl.
+
+use ::core::fmt::Debug;
+
+use crate::{FieldDelta, ReplaceDelta};
+
+/// Trait for structs which may compute the fields differing between two values.
+///
+/// Should be derived alongside [Fields][crate::Fields].
+pub trait Diff
+where
+    Self: ReplaceDelta + Sized,
+{
+    /// Get a patch of the fields differing between `old` and `new`, in field order.
+    ///
+    /// Applying the patch to `old` results in a value equal to `new` for all diffed fields.
+    fn diff(old: &Self, new: &Self) -> Patch<Self>;
+}
+
+/// Ordered field changes of a struct, storing both old and new values
+/// such that the patch may be inverted.
+pub struct Patch<T: FieldDelta> {
+    /// Deltas with old values.
+    old: Vec<T::FieldDelta>,
+    /// Deltas with new values.
+    new: Vec<T::FieldDelta>,
+}
+
+impl<T: FieldDelta> Patch<T> {
+    /// Create an empty patch.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            old: Vec::new(),
+            new: Vec::new(),
+        }
+    }
+
+    /// Add a change of a field, from the `old` delta to the `new` delta.
+    ///
+    /// Both deltas should be of the same field.
+    #[inline]
+    pub fn push(&mut self, old: T::FieldDelta, new: T::FieldDelta) {
+        self.old.push(old);
+        self.new.push(new);
+    }
+
+    /// Get amount of changed fields.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.new.len()
+    }
+
+    /// Check if no fields are changed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.new.is_empty()
+    }
+
+    /// Get deltas with old values.
+    #[inline]
+    pub fn old_values(&self) -> &[T::FieldDelta] {
+        &self.old
+    }
+
+    /// Get deltas with new values.
+    #[inline]
+    pub fn new_values(&self) -> &[T::FieldDelta] {
+        &self.new
+    }
+
+    /// Iterate changes as pairs of old and new deltas.
+    pub fn changes(&self) -> impl Iterator<Item = (&T::FieldDelta, &T::FieldDelta)> {
+        self.old.iter().zip(&self.new)
+    }
+
+    /// Iterate indices of changed fields, such as for marking them as changed.
+    pub fn fields<'a, I>(&'a self) -> impl Iterator<Item = &'a I>
+    where
+        T::FieldDelta: AsRef<I>,
+        I: 'a + ?Sized,
+    {
+        self.new.iter().map(AsRef::as_ref)
+    }
+
+    /// Swap old and new values, such that applying the patch reverts it.
+    #[must_use]
+    pub fn invert(self) -> Self {
+        Self {
+            old: self.new,
+            new: self.old,
+        }
+    }
+
+    /// Split patch into deltas with old values and deltas with new values.
+    pub fn into_parts(self) -> (Vec<T::FieldDelta>, Vec<T::FieldDelta>) {
+        (self.old, self.new)
+    }
+
+    /// Apply new values to target.
+    pub fn apply(&self, target: &mut T)
+    where
+        T::FieldDelta: Clone,
+    {
+        for delta in &self.new {
+            target.delta(delta.clone());
+        }
+    }
+
+    /// Apply new values to target, consuming the patch.
+    pub fn apply_owned(self, target: &mut T) {
+        for delta in self.new {
+            target.delta(delta);
+        }
+    }
+}
+
+impl<T: FieldDelta> Default for Patch<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FieldDelta> Clone for Patch<T>
+where
+    T::FieldDelta: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            old: self.old.clone(),
+            new: self.new.clone(),
+        }
+    }
+}
+
+impl<T: FieldDelta> Debug for Patch<T>
+where
+    T::FieldDelta: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("Patch")
+            .field("old", &self.old)
+            .field("new", &self.new)
+            .finish()
+    }
+}
+
+/// Undo and redo history of patches.
+///
+/// Patches are applied using [ReplaceDelta::replace_delta], storing only the replaced values,
+/// as such deltas need not be [Clone].
+pub struct History<T: ReplaceDelta> {
+    /// Deltas restoring the state before applied patches, most recent last.
+    undo: Vec<Vec<T::FieldDelta>>,
+    /// Deltas restoring the state before undone patches, most recently undone last.
+    redo: Vec<Vec<T::FieldDelta>>,
+}
+
+impl<T: ReplaceDelta> History<T> {
+    /// Create an empty history.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Apply deltas to target in reverse order, returning the replaced values.
+    fn swap(target: &mut T, deltas: Vec<T::FieldDelta>) -> Vec<T::FieldDelta> {
+        deltas
+            .into_iter()
+            .rev()
+            .map(|delta| target.replace_delta(delta))
+            .collect()
+    }
+
+    /// Record an already applied patch, clearing redo history.
+    ///
+    /// Empty patches are ignored.
+    pub fn push(&mut self, patch: Patch<T>) {
+        if !patch.is_empty() {
+            self.undo.push(patch.old);
+            self.redo.clear();
+        }
+    }
+
+    /// Change target to `new`, recording the difference.
+    pub fn record(&mut self, target: &mut T, new: T)
+    where
+        T: Diff,
+    {
+        self.push(T::diff(target, &new));
+        *target = new;
+    }
+
+    /// Apply a patch to target and record it, clearing redo history.
+    ///
+    /// Empty patches are ignored.
+    pub fn apply(&mut self, target: &mut T, patch: Patch<T>) {
+        if !patch.is_empty() {
+            // Reversed as swap applies deltas in reverse order.
+            let mut new = patch.new;
+            new.reverse();
+            self.undo.push(Self::swap(target, new));
+            self.redo.clear();
+        }
+    }
+
+    /// Revert the most recent patch of target, returning false if there is nothing to undo.
+    pub fn undo(&mut self, target: &mut T) -> bool {
+        self.undo.pop().is_some_and(|deltas| {
+            self.redo.push(Self::swap(target, deltas));
+            true
+        })
+    }
+
+    /// Reapply the most recently undone patch to target,
+    /// returning false if there is nothing to redo.
+    pub fn redo(&mut self, target: &mut T) -> bool {
+        self.redo.pop().is_some_and(|deltas| {
+            self.undo.push(Self::swap(target, deltas));
+            true
+        })
+    }
+
+    /// Check if there are patches to undo.
+    #[inline]
+    pub const fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Check if there are patches to redo.
+    #[inline]
+    pub const fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Remove all history.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<T: ReplaceDelta> Default for History<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ReplaceDelta> Debug for History<T>
+where
+    T::FieldDelta: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("History")
+            .field("undo", &self.undo)
+            .field("redo", &self.redo)
+            .finish()
+    }
+}
@@ -0,0 +1,386 @@
+//! [EnumMap] impl.
+
+use ::core::{
+    fmt::Debug,
+    iter::Zip,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::{AsStr, Variants};
+
+/// A map with a value for every variant of `K`.
+///
+/// Values are stored densely in variant order, indexed using [Variants::index_of].
+/// Storage is a boxed slice of length `K::VARIANTS.len()` since the length of an array
+/// cannot depend on a generic constant.
+pub struct EnumMap<K, V> {
+    /// Values, one for each variant.
+    values: Box<[V]>,
+    /// Key type.
+    _key: PhantomData<fn(K) -> K>,
+}
+
+impl<K: Variants, V> EnumMap<K, V> {
+    /// Create a map by calling `f` for every variant in variant order.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(&K) -> V,
+    {
+        Self {
+            values: K::VARIANTS.iter().map(f).collect(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Create a map from values in variant order.
+    ///
+    /// # Errors
+    /// If the amount of values is not the same as the amount of variants,
+    /// the values are returned.
+    pub fn try_from_values(values: Vec<V>) -> Result<Self, Vec<V>> {
+        if values.len() == K::VARIANTS.len() {
+            Ok(Self {
+                values: values.into_boxed_slice(),
+                _key: PhantomData,
+            })
+        } else {
+            Err(values)
+        }
+    }
+
+    /// Map every value, creating a new map.
+    pub fn map<U, F>(self, mut f: F) -> EnumMap<K, U>
+    where
+        F: FnMut(&K, V) -> U,
+    {
+        EnumMap {
+            values: K::VARIANTS
+                .iter()
+                .zip(self.values)
+                .map(|(key, value)| f(key, value))
+                .collect(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Get a reference to the value of a key.
+    #[inline]
+    pub fn get(&self, key: &K) -> &V {
+        &self.values[key.index_of()]
+    }
+
+    /// Get a mutable reference to the value of a key.
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        &mut self.values[key.index_of()]
+    }
+
+    /// Replace the value of a key, returning the previous value.
+    #[inline]
+    pub fn insert(&mut self, key: &K, value: V) -> V {
+        ::core::mem::replace(self.get_mut(key), value)
+    }
+
+    /// Amount of entries in map, always the same as the amount of variants.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if map has no entries, only true if `K` has no variants.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterate keys in variant order.
+    #[inline]
+    pub fn keys(&self) -> slice::Iter<'static, K> {
+        K::VARIANTS.iter()
+    }
+
+    /// Iterate values in variant order.
+    #[inline]
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Iterate mutable values in variant order.
+    #[inline]
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Iterate entries in variant order.
+    #[inline]
+    pub fn iter(&self) -> Zip<slice::Iter<'static, K>, slice::Iter<'_, V>> {
+        K::VARIANTS.iter().zip(self.values.iter())
+    }
+
+    /// Iterate entries with mutable values in variant order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Zip<slice::Iter<'static, K>, slice::IterMut<'_, V>> {
+        K::VARIANTS.iter().zip(self.values.iter_mut())
+    }
+
+    /// Get values as a slice in variant order.
+    #[inline]
+    pub const fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Get values as a mutable slice in variant order.
+    #[inline]
+    pub const fn as_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Get a [Debug] implementor using [AsStr] names of keys.
+    pub fn debug_as_str(&self) -> impl Debug + '_
+    where
+        K: AsStr,
+        V: Debug,
+    {
+        /// Debug wrapper using as_str for keys.
+        struct DebugAsStr<'a, K, V>(&'a EnumMap<K, V>);
+
+        impl<K: Variants + AsStr, V: Debug> Debug for DebugAsStr<'_, K, V> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_map()
+                    .entries(self.0.iter().map(|(key, value)| (key.as_str(), value)))
+                    .finish()
+            }
+        }
+
+        DebugAsStr(self)
+    }
+}
+
+impl<K: Variants, V: Default> Default for EnumMap<K, V> {
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<K, V: Clone> Clone for EnumMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K, V: PartialEq> PartialEq for EnumMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<K, V: Eq> Eq for EnumMap<K, V> {}
+
+impl<K, V: ::core::hash::Hash> ::core::hash::Hash for EnumMap<K, V> {
+    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<K: Variants + Debug, V: Debug> Debug for EnumMap<K, V> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Variants, V> Index<K> for EnumMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, index: K) -> &Self::Output {
+        self.get(&index)
+    }
+}
+
+impl<K: Variants, V> IndexMut<K> for EnumMap<K, V> {
+    #[inline]
+    fn index_mut(&mut self, index: K) -> &mut Self::Output {
+        self.get_mut(&index)
+    }
+}
+
+impl<'a, K: Variants, V> IntoIterator for &'a EnumMap<K, V> {
+    type Item = (&'static K, &'a V);
+    type IntoIter = Zip<slice::Iter<'static, K>, slice::Iter<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Variants, V> IntoIterator for &'a mut EnumMap<K, V> {
+    type Item = (&'static K, &'a mut V);
+    type IntoIter = Zip<slice::Iter<'static, K>, slice::IterMut<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Variants, V> IntoIterator for EnumMap<K, V> {
+    type Item = (&'static K, V);
+    type IntoIter = Zip<slice::Iter<'static, K>, ::std::vec::IntoIter<V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        K::VARIANTS.iter().zip(self.values.into_vec())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    //! Serde implementations for [EnumMap].
+
+    use ::core::{fmt::Formatter, marker::PhantomData};
+
+    use ::serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, MapAccess, Visitor},
+    };
+
+    use crate::{AsStr, EnumMap, Variants};
+
+    impl<K, V> Serialize for EnumMap<K, V>
+    where
+        K: Variants + Serialize,
+        V: Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_map(self.iter())
+        }
+    }
+
+    impl<'de, K, V> Deserialize<'de> for EnumMap<K, V>
+    where
+        K: Variants + AsStr + Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            /// Visitor collecting entries of map.
+            struct EnumMapVisitor<K, V>(PhantomData<fn() -> (K, V)>);
+
+            impl<'de, K, V> Visitor<'de> for EnumMapVisitor<K, V>
+            where
+                K: Variants + AsStr + Deserialize<'de>,
+                V: Deserialize<'de>,
+            {
+                type Value = EnumMap<K, V>;
+
+                fn expecting(&self, formatter: &mut Formatter) -> ::core::fmt::Result {
+                    write!(
+                        formatter,
+                        "a map with an entry for each of {} variants",
+                        K::VARIANTS.len()
+                    )
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: MapAccess<'de>,
+                {
+                    let mut values = K::VARIANTS.iter().map(|_| None).collect::<Vec<_>>();
+                    while let Some((key, value)) = map.next_entry::<K, V>()? {
+                        if values[key.index_of()].replace(value).is_some() {
+                            return Err(A::Error::custom("duplicate entry in map"));
+                        }
+                    }
+
+                    values
+                        .into_iter()
+                        .zip(K::VARIANTS)
+                        .map(|(value, key)| {
+                            value.ok_or_else(|| {
+                                A::Error::custom(format_args!(
+                                    "missing entry for variant {}",
+                                    key.as_str()
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(|values| EnumMap {
+                            values: values.into_boxed_slice(),
+                            _key: PhantomData,
+                        })
+                }
+            }
+
+            deserializer.deserialize_map(EnumMapVisitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Key {
+        A,
+        B,
+        C,
+    }
+
+    // SAFETY: VARIANTS contains all variants and index_of matches it.
+    unsafe impl Variants for Key {
+        const VARIANTS: &[Self] = &[Key::A, Key::B, Key::C];
+
+        fn index_of(&self) -> usize {
+            *self as usize
+        }
+    }
+
+    impl AsStr for Key {
+        fn as_str<'a>(&self) -> &'a str {
+            match self {
+                Key::A => "a",
+                Key::B => "b",
+                Key::C => "c",
+            }
+        }
+    }
+
+    #[test]
+    fn enum_map_index() {
+        let mut map = EnumMap::<Key, u32>::default();
+        map[Key::B] = 5;
+        *map.get_mut(&Key::C) += 2;
+
+        assert_eq!(map.as_slice(), &[0, 5, 2]);
+        assert_eq!(map[Key::B], 5);
+        assert_eq!(map.insert(&Key::A, 1), 0);
+        assert_eq!(map[Key::A], 1);
+    }
+
+    #[test]
+    fn enum_map_iter_order() {
+        let map = EnumMap::from_fn(|key: &Key| key.index_of() * 10).map(|_, value| value + 1);
+
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(&Key::A, &1), (&Key::B, &11), (&Key::C, &21)]
+        );
+        assert_eq!(
+            format!("{:?}", map.debug_as_str()),
+            r#"{"a": 1, "b": 11, "c": 21}"#
+        );
+    }
+}
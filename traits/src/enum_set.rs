@@ -0,0 +1,396 @@
+//! [EnumSet] impl.
+
+use ::core::{
+    fmt::{Debug, Display},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{AsStr, Variants};
+
+/// A set of variants of `K` stored as a bitset.
+///
+/// Bits are indexed using [Variants::index_of], as such `K` may have at most 128 variants,
+/// which is checked at compile time.
+pub struct EnumSet<K> {
+    /// Bit for every variant, set if variant is part of set.
+    bits: u128,
+    /// Key type.
+    _key: PhantomData<fn(K) -> K>,
+}
+
+impl<K: Variants> EnumSet<K> {
+    /// Bits of all variants.
+    const ALL_BITS: u128 = {
+        let len = K::VARIANTS.len();
+        assert!(len <= 128, "EnumSet supports at most 128 variants");
+        if len == 0 {
+            0
+        } else {
+            u128::MAX >> (128 - len)
+        }
+    };
+
+    /// Create a set from raw bits.
+    #[inline]
+    const fn from_bits(bits: u128) -> Self {
+        Self {
+            bits: bits & Self::ALL_BITS,
+            _key: PhantomData,
+        }
+    }
+
+    /// Get bit of a variant.
+    #[inline]
+    fn bit(value: &K) -> u128 {
+        1 << value.index_of()
+    }
+
+    /// Create an empty set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// Create a set containing all variants.
+    #[inline]
+    pub const fn all() -> Self {
+        Self::from_bits(Self::ALL_BITS)
+    }
+
+    /// Insert a value, returning true if it was not already in set.
+    #[inline]
+    pub fn insert(&mut self, value: &K) -> bool {
+        let bit = Self::bit(value);
+        let inserted = self.bits & bit == 0;
+        self.bits |= bit;
+        inserted
+    }
+
+    /// Remove a value, returning true if it was in set.
+    #[inline]
+    pub fn remove(&mut self, value: &K) -> bool {
+        let bit = Self::bit(value);
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+        removed
+    }
+
+    /// Insert value if not in set, otherwise remove it.
+    /// Returns true if value is in set afterwards.
+    #[inline]
+    pub fn toggle(&mut self, value: &K) -> bool {
+        self.bits ^= Self::bit(value);
+        self.contains(value)
+    }
+
+    /// Check if set contains a value.
+    #[inline]
+    pub fn contains(&self, value: &K) -> bool {
+        self.bits & Self::bit(value) != 0
+    }
+
+    /// Remove all values.
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.bits = 0;
+    }
+
+    /// Amount of values in set.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Check if set is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Check if all values of self are in other.
+    #[inline]
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.bits & other.bits == self.bits
+    }
+
+    /// Check if all values of other are in self.
+    #[inline]
+    pub const fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Check if self and other have no values in common.
+    #[inline]
+    pub const fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// Iterate values in variant order.
+    #[inline]
+    pub const fn iter(&self) -> EnumSetIter<K> {
+        EnumSetIter {
+            bits: self.bits,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K: Variants> Default for EnumSet<K> {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K> Clone for EnumSet<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for EnumSet<K> {}
+
+impl<K> PartialEq for EnumSet<K> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<K> Eq for EnumSet<K> {}
+
+impl<K> ::core::hash::Hash for EnumSet<K> {
+    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<K: Variants + Debug> Debug for EnumSet<K> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Formats values as a comma-separated list using [AsStr].
+impl<K: Variants + AsStr> Display for EnumSet<K> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        let mut iter = self.iter();
+        if let Some(first) = iter.next() {
+            f.write_str(first.as_str())?;
+        }
+        for value in iter {
+            f.write_str(",")?;
+            f.write_str(value.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a comma-separated list of values, surrounding whitespace is ignored.
+impl<K: Variants + FromStr> FromStr for EnumSet<K> {
+    type Err = K::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::empty();
+        for value in s
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            set.insert(&value.parse()?);
+        }
+        Ok(set)
+    }
+}
+
+impl<K: Variants> FromIterator<K> for EnumSet<K> {
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, K: Variants> FromIterator<&'a K> for EnumSet<K> {
+    fn from_iter<T: IntoIterator<Item = &'a K>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Variants> Extend<K> for EnumSet<K> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for value in iter {
+            self.insert(&value);
+        }
+    }
+}
+
+impl<'a, K: Variants> Extend<&'a K> for EnumSet<K> {
+    fn extend<T: IntoIterator<Item = &'a K>>(&mut self, iter: T) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<K: Variants> IntoIterator for EnumSet<K> {
+    type Item = &'static K;
+    type IntoIter = EnumSetIter<K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Variants> IntoIterator for &EnumSet<K> {
+    type Item = &'static K;
+    type IntoIter = EnumSetIter<K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Variants> BitOr for EnumSet<K> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits | rhs.bits)
+    }
+}
+
+impl<K: Variants> BitAnd for EnumSet<K> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits & rhs.bits)
+    }
+}
+
+impl<K: Variants> BitXor for EnumSet<K> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits ^ rhs.bits)
+    }
+}
+
+impl<K: Variants> Sub for EnumSet<K> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits & !rhs.bits)
+    }
+}
+
+/// Complement of set, only containing values of [Variants::VARIANTS].
+impl<K: Variants> Not for EnumSet<K> {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        Self::from_bits(!self.bits)
+    }
+}
+
+impl<K: Variants> BitOrAssign for EnumSet<K> {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<K: Variants> BitAndAssign for EnumSet<K> {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl<K: Variants> BitXorAssign for EnumSet<K> {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl<K: Variants> SubAssign for EnumSet<K> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Iterator over values of an [EnumSet] in variant order.
+pub struct EnumSetIter<K> {
+    /// Remaining bits to yield.
+    bits: u128,
+    /// Key type.
+    _key: PhantomData<fn(K) -> K>,
+}
+
+impl<K> Debug for EnumSetIter<K> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("EnumSetIter")
+            .field("bits", &self.bits)
+            .finish()
+    }
+}
+
+impl<K> Clone for EnumSetIter<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K: Variants> Iterator for EnumSetIter<K> {
+    type Item = &'static K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = self.bits.trailing_zeros() as usize;
+        self.bits &= self.bits - 1;
+        K::VARIANTS.get(index)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<K: Variants> DoubleEndedIterator for EnumSetIter<K> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = 127 - self.bits.leading_zeros() as usize;
+        self.bits &= !(1 << index);
+        K::VARIANTS.get(index)
+    }
+}
+
+impl<K: Variants> ExactSizeIterator for EnumSetIter<K> {}
+
+impl<K: Variants> FusedIterator for EnumSetIter<K> {}
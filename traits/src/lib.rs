@@ -2,6 +2,21 @@
 
 use ::core::{hash::Hash, mem::discriminant, str::FromStr};
 
+pub use describe::{Describe, Description, PropValue};
+pub use diff::{Diff, History, Patch};
+pub use enum_map::EnumMap;
+pub use enum_set::{EnumSet, EnumSetIter};
+pub use merge::{Merge, Merged, MergedProxy};
+pub use repr::{InvalidRepr, Repr};
+
+mod describe;
+mod diff;
+mod enum_map;
+mod enum_set;
+mod merge;
+mod repr;
+mod variant_impls;
+
 /// Trait for simple enums to provide all values.
 ///
 /// # Safety
@@ -35,7 +50,39 @@ where
     }
 }
 
+/// Trait for types with a finite amount of values which may be indexed.
+///
+/// Implemented for all [Variants] types, and for [Option], [Result] and tuples of
+/// implementors as cartesian products, where earlier values are more significant.
+/// `None` is ordered before `Some` values and `Ok` values before `Err` values.
+/// The compound types also implement [Cycle] and [PartialVariants] using this order.
+///
+/// # Safety
+/// `variant_index` must return a value less than `VARIANT_COUNT`, and
+/// `from_variant_index` must be its inverse, returning none for out of range indices.
+pub unsafe trait VariantIndex
+where
+    Self: Sized,
+{
+    /// Amount of values.
+    const VARIANT_COUNT: usize;
+
+    /// Get index of value.
+    fn variant_index(&self) -> usize;
+
+    /// Get value at index.
+    fn from_variant_index(index: usize) -> Option<Self>;
+
+    /// Iterate all values in index order.
+    fn variants() -> impl Iterator<Item = Self> {
+        (0..Self::VARIANT_COUNT).filter_map(Self::from_variant_index)
+    }
+}
+
 /// Trait to provide a set of variants.
+///
+/// Implemented for all types implementing [Variants] and [Cycle], using the variants cycled
+/// by [Cycle]. May be derived for simple enums not implementing both.
 pub trait PartialVariants {
     /// Provide an iterator of variants.
     fn partial_variants<'a>() -> impl IntoIterator<Item = &'a Self>
@@ -105,7 +152,7 @@ where
     where
         Self: 'a,
     {
-        T::VARIANTS
+        T::cycled_variants().unwrap_or(T::VARIANTS)
     }
 
     #[inline]
@@ -130,12 +177,25 @@ where
 /// # Safety
 /// `cycle_next` must return the cyclic next variant in `VARIANTS`.
 /// `cycle_prev` must return the cyclic previous variant in `VARIANTS`.
+/// For compound types without `VARIANTS` the order of [VariantIndex] is used.
+/// If `cycled_variants` returns some variants, those are cycled in their order instead, with
+/// other variants cycling to the first and last of them. Saturating implementations return
+/// the last and first variant instead of wrapping around.
 pub unsafe trait Cycle {
     /// Get the next variant. For the last variant will return the first variant.
     fn cycle_next(&self) -> Self;
 
     /// Get the next variant. For the last variant will return the first variant.
     fn cycle_prev(&self) -> Self;
+
+    /// Get the cycled variants in cycle order, if not all variants in their order.
+    #[inline]
+    fn cycled_variants() -> Option<&'static [Self]>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Trait for getting the name of an enum variant.
@@ -162,6 +222,15 @@ pub trait Proxy {
     fn proxy(&self) -> &Self::Proxy;
 }
 
+/// Provide mutable access to the proxy struct, for proxies with setters.
+pub trait ProxyMut
+where
+    Self: Proxy,
+{
+    /// Return mutable proxy object.
+    fn proxy_mut(&mut self) -> &mut Self::Proxy;
+}
+
 /// Convert a struct to it's fields.
 pub trait IntoFields {
     /// Representation of any non-skipped field.
@@ -183,6 +252,17 @@ pub trait FieldDelta {
     fn delta(&mut self, delta: Self::FieldDelta);
 }
 
+/// Apply a field as a delta returning the replaced value, used to revert changes.
+///
+/// Implemented by the [Fields] derive.
+pub trait ReplaceDelta
+where
+    Self: FieldDelta,
+{
+    /// Apply a single field as a change to self, returning the replaced value of the field.
+    fn replace_delta(&mut self, delta: Self::FieldDelta) -> Self::FieldDelta;
+}
+
 /// Trait for structs providing an indexing enum to index fields.
 pub trait FieldsIdx {
     /// Type to index fields with.
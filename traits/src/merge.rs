@@ -0,0 +1,197 @@
+//! [Merge] trait, [Merged] and [MergedProxy] impl.
+
+use ::core::{fmt::Debug, ops::Deref};
+
+use crate::{EnumMap, FieldsIdx, Proxy, Variants};
+
+/// Trait for structs of mostly optional fields which may be combined
+/// with values of lower precedence, such as configuration layers.
+///
+/// Should be derived alongside [Fields][crate::Fields].
+pub trait Merge
+where
+    Self: Sized + FieldsIdx,
+{
+    /// Set fields which are not set in self using the fields of `lower`.
+    fn merge_from(&mut self, lower: Self);
+
+    /// Check if a field is set, non-optional fields are always set.
+    fn is_set(&self, idx: Self::FieldIdx) -> bool;
+
+    /// Combine self with `lower`, keeping set fields of self.
+    #[must_use]
+    fn merge(mut self, lower: Self) -> Self {
+        self.merge_from(lower);
+        self
+    }
+}
+
+/// Value merged from layers, recording which layer set each field.
+///
+/// Layers are merged from the highest precedence to the lowest.
+pub struct Merged<T: FieldsIdx, L> {
+    /// Merged value.
+    value: T,
+    /// Merged layers, in order of precedence.
+    layers: Vec<L>,
+    /// Index of the layer which set each field.
+    sources: EnumMap<T::FieldIdx, Option<usize>>,
+}
+
+impl<T, L> Merged<T, L>
+where
+    T: Merge,
+    T::FieldIdx: Variants + Copy,
+{
+    /// Create a merged value from the highest precedence layer.
+    pub fn new(value: T, layer: L) -> Self {
+        Self {
+            sources: EnumMap::from_fn(|idx| value.is_set(*idx).then_some(0)),
+            value,
+            layers: vec![layer],
+        }
+    }
+
+    /// Merge a layer with lower precedence than all currently merged layers.
+    pub fn merge_from(&mut self, lower: T, layer: L) {
+        let index = self.layers.len();
+        for (idx, source) in self.sources.iter_mut() {
+            if source.is_none() && lower.is_set(*idx) {
+                *source = Some(index);
+            }
+        }
+        self.value.merge_from(lower);
+        self.layers.push(layer);
+    }
+
+    /// Merge a layer with lower precedence than all currently merged layers.
+    #[must_use]
+    pub fn merge(mut self, lower: T, layer: L) -> Self {
+        self.merge_from(lower, layer);
+        self
+    }
+
+    /// Get the layer which set a field, none if no layer set it.
+    pub fn source_of(&self, idx: T::FieldIdx) -> Option<&L> {
+        source_of(&self.layers, &self.sources, idx)
+    }
+
+    /// Get a proxy of the merged value which may also get the layer which set a field.
+    pub fn proxy(&self) -> MergedProxy<'_, T, L>
+    where
+        T: Proxy,
+    {
+        MergedProxy {
+            proxy: self.value.proxy(),
+            layers: &self.layers,
+            sources: &self.sources,
+        }
+    }
+}
+
+impl<T: FieldsIdx, L> Merged<T, L> {
+    /// Get merged value.
+    #[inline]
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Get merged layers, in order of precedence.
+    #[inline]
+    pub fn layers(&self) -> &[L] {
+        &self.layers
+    }
+
+    /// Convert into merged value.
+    #[inline]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: FieldsIdx, L> AsRef<T> for Merged<T, L> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, L> Debug for Merged<T, L>
+where
+    T: FieldsIdx + Debug,
+    T::FieldIdx: Variants + Debug,
+    L: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("Merged")
+            .field("value", &self.value)
+            .field("layers", &self.layers)
+            .field("sources", &self.sources)
+            .finish()
+    }
+}
+
+/// Proxy of a [Merged] value, dereferencing to the proxy of the value.
+pub struct MergedProxy<'a, T: FieldsIdx + Proxy, L> {
+    /// Proxy of merged value.
+    proxy: &'a T::Proxy,
+    /// Merged layers, in order of precedence.
+    layers: &'a [L],
+    /// Index of the layer which set each field.
+    sources: &'a EnumMap<T::FieldIdx, Option<usize>>,
+}
+
+impl<'a, T, L> MergedProxy<'a, T, L>
+where
+    T: FieldsIdx + Proxy,
+    T::FieldIdx: Variants,
+{
+    /// Get the layer which set a field, none if no layer set it
+    /// and the proxy uses the default value.
+    pub fn source_of(&self, idx: T::FieldIdx) -> Option<&'a L> {
+        source_of(self.layers, self.sources, idx)
+    }
+}
+
+impl<T: FieldsIdx + Proxy, L> Deref for MergedProxy<'_, T, L> {
+    type Target = T::Proxy;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.proxy
+    }
+}
+
+impl<T, L> Debug for MergedProxy<'_, T, L>
+where
+    T: FieldsIdx + Proxy,
+    T::Proxy: Debug,
+    T::FieldIdx: Variants + Debug,
+    L: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("MergedProxy")
+            .field("proxy", &self.proxy)
+            .field("layers", &self.layers)
+            .field("sources", &self.sources)
+            .finish()
+    }
+}
+
+impl<T: FieldsIdx + Proxy, L> Clone for MergedProxy<'_, T, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: FieldsIdx + Proxy, L> Copy for MergedProxy<'_, T, L> {}
+
+/// Get the layer which set a field.
+fn source_of<'a, K: Variants, L>(
+    layers: &'a [L],
+    sources: &EnumMap<K, Option<usize>>,
+    idx: K,
+) -> Option<&'a L> {
+    sources[idx].and_then(|index| layers.get(index))
+}
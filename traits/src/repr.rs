@@ -0,0 +1,72 @@
+//! [Repr] trait and [InvalidRepr] error.
+
+use ::core::fmt::{Debug, Display};
+
+/// Trait for fieldless enums with an integer representation.
+///
+/// Derived implementations keep [Repr::REPRS] aligned with
+/// [Variants::VARIANTS][crate::Variants::VARIANTS], as such for enums deriving both
+/// `Self::REPRS[value.index_of()] == value.to_repr()`.
+pub trait Repr
+where
+    Self: Sized,
+{
+    /// Integer type of representation.
+    type Repr: 'static + Copy;
+
+    /// Representations of all variants in declaration order.
+    const REPRS: &'static [Self::Repr];
+
+    /// Get representation of current variant.
+    fn to_repr(&self) -> Self::Repr;
+
+    /// Get variant with the given representation.
+    ///
+    /// # Errors
+    /// If no variant has the given representation.
+    fn from_repr(repr: Self::Repr) -> Result<Self, InvalidRepr<Self::Repr>>;
+}
+
+/// Error returned when converting an integer without a matching variant to an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InvalidRepr<R> {
+    /// Value that did not match any variant.
+    value: R,
+    /// Name of type that was converted to.
+    type_name: &'static str,
+}
+
+impl<R> InvalidRepr<R> {
+    /// Create an error for a value which does not match any variant of type named `type_name`.
+    #[cold]
+    pub const fn new(value: R, type_name: &'static str) -> Self {
+        Self { value, type_name }
+    }
+
+    /// Get value that did not match any variant.
+    pub const fn value(&self) -> &R {
+        &self.value
+    }
+
+    /// Consume error returning value that did not match any variant.
+    pub fn into_value(self) -> R {
+        self.value
+    }
+
+    /// Get name of type that was converted to.
+    pub const fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl<R: Display> Display for InvalidRepr<R> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(
+            f,
+            "value {} does not match any variant of {}",
+            self.value, self.type_name
+        )
+    }
+}
+
+impl<R: Debug + Display> ::core::error::Error for InvalidRepr<R> {}
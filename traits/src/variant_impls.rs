@@ -0,0 +1,316 @@
+//! [Variants], [VariantIndex], [Cycle] and [PartialVariants] implementations for core types.
+
+use ::core::any::{Any, TypeId};
+use ::std::{
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use crate::{Cycle, PartialVariants, VariantIndex, Variants};
+
+// SAFETY: Both values are listed in order of their index.
+unsafe impl Variants for bool {
+    const VARIANTS: &[Self] = &[false, true];
+
+    #[inline]
+    fn index_of(&self) -> usize {
+        usize::from(*self)
+    }
+}
+
+// SAFETY: `!` returns the other value which is both next and previous.
+unsafe impl Cycle for bool {
+    #[inline]
+    fn cycle_next(&self) -> Self {
+        !*self
+    }
+
+    #[inline]
+    fn cycle_prev(&self) -> Self {
+        !*self
+    }
+}
+
+// SAFETY: There is only one value.
+unsafe impl Variants for () {
+    const VARIANTS: &[Self] = &[()];
+
+    #[inline]
+    fn index_of(&self) -> usize {
+        0
+    }
+}
+
+// SAFETY: There is only one value.
+unsafe impl Cycle for () {
+    #[inline]
+    fn cycle_next(&self) -> Self {}
+
+    #[inline]
+    fn cycle_prev(&self) -> Self {}
+}
+
+// SAFETY: Uses index_of and VARIANTS of Variants implementation.
+unsafe impl<T> VariantIndex for T
+where
+    T: Variants + Clone,
+{
+    const VARIANT_COUNT: usize = T::VARIANTS.len();
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        self.index_of()
+    }
+
+    #[inline]
+    fn from_variant_index(index: usize) -> Option<Self> {
+        T::VARIANTS.get(index).cloned()
+    }
+}
+
+// SAFETY: None has index 0, followed by all values of T.
+unsafe impl<T> VariantIndex for Option<T>
+where
+    T: VariantIndex,
+{
+    const VARIANT_COUNT: usize = T::VARIANT_COUNT + 1;
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        match self {
+            None => 0,
+            Some(value) => value.variant_index() + 1,
+        }
+    }
+
+    #[inline]
+    fn from_variant_index(index: usize) -> Option<Self> {
+        match index.checked_sub(1) {
+            None => Some(None),
+            Some(index) => T::from_variant_index(index).map(Some),
+        }
+    }
+}
+
+// SAFETY: All values of T are followed by all values of E.
+unsafe impl<T, E> VariantIndex for Result<T, E>
+where
+    T: VariantIndex,
+    E: VariantIndex,
+{
+    const VARIANT_COUNT: usize = T::VARIANT_COUNT + E::VARIANT_COUNT;
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        match self {
+            Ok(value) => value.variant_index(),
+            Err(err) => T::VARIANT_COUNT + err.variant_index(),
+        }
+    }
+
+    #[inline]
+    fn from_variant_index(index: usize) -> Option<Self> {
+        match index.checked_sub(T::VARIANT_COUNT) {
+            None => T::from_variant_index(index).map(Ok),
+            Some(index) => E::from_variant_index(index).map(Err),
+        }
+    }
+}
+
+/// Get the cyclic next or previous value using [VariantIndex].
+fn cycle_variant_index<T: VariantIndex>(value: &T, forwards: bool) -> T {
+    let count = T::VARIANT_COUNT;
+    let index = value.variant_index();
+    let index = if forwards {
+        (index + 1) % count
+    } else {
+        index.checked_sub(1).unwrap_or(count - 1)
+    };
+    T::from_variant_index(index).expect("every index less than VARIANT_COUNT should have a value")
+}
+
+/// Get all values of a [VariantIndex] type in index order, as statics may not use generics.
+/// Values are leaked once for every type, after which they are looked up under a read lock.
+fn leaked_variants<T>() -> &'static [T]
+where
+    T: VariantIndex + Any + Send + Sync,
+{
+    /// Leaked values by type.
+    type Leaked = HashMap<TypeId, &'static (dyn Any + Send + Sync)>;
+    static LEAKED: OnceLock<RwLock<Leaked>> = OnceLock::new();
+
+    let leaked = LEAKED.get_or_init(RwLock::default);
+    let key = TypeId::of::<T>();
+    let cached = leaked
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+        .copied();
+    let value = cached.unwrap_or_else(|| {
+        *leaked
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(T::variants().collect::<Vec<T>>())))
+    });
+
+    value
+        .downcast_ref::<Vec<T>>()
+        .expect("leaked values should be keyed by their type")
+}
+
+/// Get a value of a [VariantIndex] type by index with any lifetime.
+fn variant_index_cast<'a, T>(value: &T) -> &'a T
+where
+    T: VariantIndex + Any + Send + Sync,
+{
+    leaked_variants::<T>()
+        .get(value.variant_index())
+        .expect("variant index should be less than VARIANT_COUNT")
+}
+
+/// Implement [Cycle] and [PartialVariants] using [VariantIndex].
+macro_rules! cycle_impl {
+    ([$($param:ident),*] $ty:ty) => {
+        // SAFETY: Cycles using index order of VariantIndex.
+        unsafe impl<$($param: VariantIndex),*> Cycle for $ty {
+            #[inline]
+            fn cycle_next(&self) -> Self {
+                cycle_variant_index(self, true)
+            }
+
+            #[inline]
+            fn cycle_prev(&self) -> Self {
+                cycle_variant_index(self, false)
+            }
+        }
+
+        // Values are leaked once per type and looked up in a global table on every call.
+        impl<$($param: VariantIndex + Any + Send + Sync),*> PartialVariants for $ty {
+            #[inline]
+            fn partial_variants<'a>() -> impl IntoIterator<Item = &'a Self>
+            where
+                Self: 'a,
+            {
+                leaked_variants::<Self>()
+            }
+
+            #[inline]
+            fn partial_cycle_next<'a>(&self) -> &'a Self
+            where
+                Self: 'a + PartialEq,
+            {
+                variant_index_cast(&Cycle::cycle_next(self))
+            }
+
+            #[inline]
+            fn partial_cycle_prev<'a>(&self) -> &'a Self
+            where
+                Self: 'a + PartialEq,
+            {
+                variant_index_cast(&Cycle::cycle_prev(self))
+            }
+        }
+    };
+}
+
+cycle_impl!([T] Option<T>);
+cycle_impl!([T, E] Result<T, E>);
+
+/// Implement [VariantIndex], [Cycle] and [PartialVariants] for a tuple.
+macro_rules! tuple_impl {
+    ($($param:ident $idx:tt),+) => {
+        // SAFETY: Index is a mixed radix number where the first element is most significant.
+        unsafe impl<$($param: VariantIndex),+> VariantIndex for ($($param,)+) {
+            const VARIANT_COUNT: usize = 1 $(* $param::VARIANT_COUNT)+;
+
+            #[inline]
+            fn variant_index(&self) -> usize {
+                let index = 0;
+                $(
+                let index = index * $param::VARIANT_COUNT + self.$idx.variant_index();
+                )+
+                index
+            }
+
+            #[inline]
+            fn from_variant_index(index: usize) -> Option<Self> {
+                if index >= Self::VARIANT_COUNT {
+                    return None;
+                }
+                let counts = [$($param::VARIANT_COUNT),+];
+                Some(($(
+                    $param::from_variant_index(
+                        index / counts[$idx + 1..].iter().product::<usize>() % $param::VARIANT_COUNT,
+                    )?,
+                )+))
+            }
+        }
+
+        cycle_impl!([$($param),+] ($($param,)+));
+    };
+}
+
+tuple_impl!(A 0);
+tuple_impl!(A 0, B 1);
+tuple_impl!(A 0, B 1, C 2);
+tuple_impl!(A 0, B 1, C 2, D 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn option_product_order() {
+        assert_eq!(
+            <Option<bool>>::variants().collect::<Vec<_>>(),
+            [None, Some(false), Some(true)]
+        );
+        assert_eq!(Some(true).cycle_next(), None);
+        assert_eq!(None::<bool>.cycle_prev(), Some(true));
+        assert_eq!(
+            <Option<bool>>::partial_variants()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [None, Some(false), Some(true)]
+        );
+        assert_eq!(*Some(true).partial_cycle_next(), None);
+        assert_eq!(*None::<bool>.partial_cycle_prev(), Some(true));
+    }
+
+    #[test]
+    fn tuple_product_order() {
+        let values = <(bool, Option<bool>)>::variants().collect::<Vec<_>>();
+
+        assert_eq!(values.len(), <(bool, Option<bool>)>::VARIANT_COUNT);
+        assert_eq!(
+            values[..3],
+            [(false, None), (false, Some(false)), (false, Some(true))]
+        );
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(value.variant_index(), index);
+        }
+        assert_eq!((false, Some(true)).cycle_next(), (true, None));
+        assert_eq!((false, None).cycle_prev(), (true, Some(true)));
+        assert_eq!(
+            <(bool, Option<bool>)>::partial_variants()
+                .into_iter()
+                .count(),
+            values.len()
+        );
+        assert_eq!(*(false, Some(true)).partial_cycle_next(), (true, None));
+    }
+
+    #[test]
+    fn result_product_order() {
+        assert_eq!(
+            <Result<bool, ()>>::variants().collect::<Vec<_>>(),
+            [Ok(false), Ok(true), Err(())]
+        );
+        assert_eq!(<Result<bool, ()>>::from_variant_index(3), None);
+        assert_eq!(*Err::<bool, ()>(()).partial_cycle_next(), Ok(false));
+        assert_eq!(*Ok::<bool, ()>(false).partial_cycle_prev(), Err(()));
+    }
+}